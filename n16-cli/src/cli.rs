@@ -1,7 +1,8 @@
 use clap::{Parser, Subcommand};
 
 mod bar;
-mod launcher;
+pub mod launcher;
+pub mod theme;
 
 #[derive(Parser, Debug)]
 pub struct Cli {
@@ -13,4 +14,7 @@ pub struct Cli {
 pub enum Command {
   Launcher(launcher::Cli),
   Bar(bar::Cli),
+  Theme(theme::Cli),
+  /// Print events from the daemon, such as failed launches, as one json object per line
+  EventStream,
 }
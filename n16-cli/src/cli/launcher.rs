@@ -1,5 +1,7 @@
-use clap::{Parser, Subcommand};
-use n16_ipc::launcher::Request;
+use std::io;
+
+use clap::{Args, Parser, Subcommand};
+use n16_ipc::launcher::{DmenuRequest, Request};
 
 #[derive(Parser, Clone, Debug)]
 
@@ -11,19 +13,81 @@ pub struct Cli {
 
 impl Cli {
   pub fn request(&self) -> n16_ipc::Request {
-    let launcher_request = match self.command {
-      Command::Open => Request::Open,
+    let launcher_request = match &self.command {
+      Command::Open(open) => Request::Open {
+        providers: open.providers(),
+        query: open.query.clone(),
+        placeholder: open.placeholder.clone(),
+      },
+      Command::Toggle(open) => Request::Toggle {
+        providers: open.providers(),
+        query: open.query.clone(),
+        placeholder: open.placeholder.clone(),
+      },
       Command::Close => Request::Close,
+      Command::Dmenu(dmenu) => Request::Dmenu(DmenuRequest {
+        items: io::stdin().lines().map_while(Result::ok).collect(),
+        prompt: dmenu.prompt.clone(),
+        password: dmenu.password,
+      }),
+      Command::Reload => Request::Reload,
     };
 
     n16_ipc::Request::Launcher(launcher_request)
   }
+
+  /// Arguments of the `dmenu` subcommand, whose response is printed to stdout
+  pub fn dmenu(&self) -> Option<&DmenuArgs> {
+    match &self.command {
+      Command::Dmenu(dmenu) => Some(dmenu),
+      _ => None,
+    }
+  }
 }
 
 #[derive(Subcommand, Clone, Debug)]
 enum Command {
   /// Open the app launcher window
-  Open,
+  Open(OpenArgs),
+  /// Close the app launcher window if it is open, otherwise open it
+  Toggle(OpenArgs),
   /// Close the app launcher window
   Close,
+  /// Choose one of the lines read from stdin and print it, like dmenu.
+  /// Exits with status 1 if the launcher is closed without choosing.
+  Dmenu(DmenuArgs),
+  /// Reload the config file, applying launcher and provider settings
+  Reload,
+}
+
+#[derive(Args, Clone, Debug)]
+struct OpenArgs {
+  /// Only show matches from this provider, such as `n16/calculator`. Can be repeated
+  #[arg(long = "provider")]
+  providers: Vec<String>,
+  /// Initial search text
+  #[arg(short, long)]
+  query: Option<String>,
+  /// Text shown in the empty search input
+  #[arg(short, long)]
+  placeholder: Option<String>,
+}
+
+impl OpenArgs {
+  fn providers(&self) -> Option<Vec<String>> {
+    (!self.providers.is_empty()).then(|| self.providers.clone())
+  }
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct DmenuArgs {
+  /// Text shown in the empty search input
+  #[arg(short, long)]
+  prompt: Option<String>,
+  /// Hide the typed text
+  #[arg(long)]
+  password: bool,
+  /// Print the index of the chosen line instead of its text, or -1 for typed text
+  #[arg(long)]
+  pub index: bool,
 }
@@ -0,0 +1,84 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use n16_ipc::theme::{Request, ThemeMode};
+
+#[derive(Parser, Clone, Debug)]
+
+/// Switch between the light and dark themes, and export the theme colors
+pub struct Cli {
+  #[command(subcommand)]
+  command: Command,
+}
+
+impl Cli {
+  pub fn request(&self) -> n16_ipc::Request {
+    let theme_request = match &self.command {
+      Command::Set { mode } => Request::SetMode((*mode).into()),
+      Command::Export(_) => Request::Get,
+    };
+
+    n16_ipc::Request::Theme(theme_request)
+  }
+
+  /// Arguments of the `export` subcommand, whose response is rendered to stdout
+  pub fn export(&self) -> Option<&ExportArgs> {
+    match &self.command {
+      Command::Export(export) => Some(export),
+      Command::Set { .. } => None,
+    }
+  }
+}
+
+#[derive(Subcommand, Clone, Debug)]
+enum Command {
+  /// Use the light or dark theme until the daemon restarts, or follow the system preference again
+  /// with `auto`
+  Set { mode: Mode },
+  /// Print the colors of the active theme in the format of another program
+  Export(ExportArgs),
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Mode {
+  Light,
+  Dark,
+  Auto,
+}
+
+impl From<Mode> for ThemeMode {
+  fn from(value: Mode) -> Self {
+    match value {
+      Mode::Light => ThemeMode::Light,
+      Mode::Dark => ThemeMode::Dark,
+      Mode::Auto => ThemeMode::Auto,
+    }
+  }
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct ExportArgs {
+  /// Built-in format to export
+  #[arg(short, long, value_enum, default_value_t = Format::Json)]
+  pub format: Format,
+  /// Render a mustache-style template instead of a built-in format, with variables such as
+  /// `{{base0D-hex}}` or `{{accent-rgb-r}}`
+  #[arg(short, long, conflicts_with = "format")]
+  pub template: Option<PathBuf>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum Format {
+  /// The palette as returned by the daemon
+  Json,
+  /// CSS custom properties
+  Css,
+  /// GTK named colors, for `gtk.css`
+  Gtk,
+  Kitty,
+  Foot,
+  /// Alacritty TOML config
+  Alacritty,
+  /// Environment variables to source in a shell
+  Shell,
+}
@@ -0,0 +1,29 @@
+use std::process::ExitCode;
+
+use n16_ipc::{Request, Response, launcher};
+
+use crate::{cli::launcher::DmenuArgs, ipc::request_response};
+
+pub fn run(request: &Request, args: &DmenuArgs) -> ExitCode {
+  let Some(selection) = request_response(request, |response| match response {
+    Response::Launcher(launcher::Response::DmenuSelection(selection)) => Ok(selection),
+    response => Err(response),
+  }) else {
+    return ExitCode::FAILURE;
+  };
+
+  let Some(selection) = selection else {
+    return ExitCode::FAILURE;
+  };
+
+  if args.index {
+    match selection.index {
+      Some(index) => println!("{index}"),
+      None => println!("-1"),
+    }
+  } else {
+    println!("{}", selection.text);
+  }
+
+  ExitCode::SUCCESS
+}
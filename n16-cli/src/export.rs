@@ -0,0 +1,121 @@
+use std::{collections::HashMap, fs, process::ExitCode};
+
+use n16_ipc::{
+  Request, Response,
+  theme::{self, Palette},
+};
+
+use crate::{
+  cli::theme::{ExportArgs, Format},
+  ipc::request_response,
+};
+
+pub fn run(request: &Request, args: &ExportArgs) -> ExitCode {
+  let Some(palette) = request_response(request, |response| match response {
+    Response::Theme(theme::Response::Palette(palette)) => Ok(palette),
+    response => Err(response),
+  }) else {
+    return ExitCode::FAILURE;
+  };
+
+  match export(&palette, args) {
+    Ok(output) => {
+      print!("{output}");
+      ExitCode::SUCCESS
+    }
+    Err(error) => {
+      eprintln!("{error}");
+      ExitCode::FAILURE
+    }
+  }
+}
+
+fn export(palette: &Palette, args: &ExportArgs) -> Result<String, String> {
+  if let Some(path) = &args.template {
+    let template = fs::read_to_string(path)
+      .map_err(|error| format!("Failed to read {}: {error}", path.display()))?;
+
+    return render(&template, &variables(palette));
+  }
+
+  if let Some(template) = built_in_template(args.format) {
+    return render(template, &variables(palette));
+  }
+
+  let json = serde_json::to_string_pretty(palette).map_err(|error| error.to_string())?;
+  Ok(json + "\n")
+}
+
+/// Template of a built-in format, or `None` for formats that aren't rendered from a template
+fn built_in_template(format: Format) -> Option<&'static str> {
+  match format {
+    Format::Json => None,
+    Format::Css => Some(include_str!("templates/css.mustache")),
+    Format::Gtk => Some(include_str!("templates/gtk.mustache")),
+    Format::Kitty => Some(include_str!("templates/kitty.mustache")),
+    Format::Foot => Some(include_str!("templates/foot.mustache")),
+    Format::Alacritty => Some(include_str!("templates/alacritty.mustache")),
+    Format::Shell => Some(include_str!("templates/shell.mustache")),
+  }
+}
+
+/// Template variables for every slot and role, following the base16 builder names:
+/// `<name>-hex` is `rrggbb`, and the `-hex-r`, `-rgb-r` and `-dec-r` suffixes give a single
+/// channel as hex, from 0 to 255 and from 0 to 1
+fn variables(palette: &Palette) -> HashMap<String, String> {
+  let mut variables = HashMap::new();
+
+  for (name, color) in palette.colors.iter().chain(&palette.roles) {
+    let hex = color.trim_start_matches('#');
+    variables.insert(format!("{name}-hex"), hex.to_string());
+
+    for (channel, range) in [("r", 0..2), ("g", 2..4), ("b", 4..6)] {
+      let Some(value) = hex
+        .get(range)
+        .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+      else {
+        continue;
+      };
+
+      variables.insert(format!("{name}-hex-{channel}"), format!("{value:02x}"));
+      variables.insert(format!("{name}-rgb-{channel}"), value.to_string());
+      variables.insert(
+        format!("{name}-dec-{channel}"),
+        format!("{:.4}", f32::from(value) / 255.0),
+      );
+    }
+  }
+
+  variables
+}
+
+/// Replace every `{{name}}` or `{{{name}}}` in a mustache-style template with its variable
+fn render(template: &str, variables: &HashMap<String, String>) -> Result<String, String> {
+  let mut output = String::with_capacity(template.len());
+  let mut rest = template;
+
+  while let Some(start) = rest.find("{{") {
+    output.push_str(&rest[..start]);
+    rest = &rest[start + 2..];
+
+    let (open, close) = if rest.starts_with('{') {
+      (1, "}}}")
+    } else {
+      (0, "}}")
+    };
+    let end = rest
+      .find(close)
+      .ok_or_else(|| "Unclosed `{{` in template".to_string())?;
+
+    let name = rest[open..end].trim();
+    let value = variables
+      .get(name)
+      .ok_or_else(|| format!("Unknown template variable `{name}`"))?;
+
+    output.push_str(value);
+    rest = &rest[end + close.len()..];
+  }
+
+  output.push_str(rest);
+  Ok(output)
+}
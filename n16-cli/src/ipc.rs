@@ -4,7 +4,7 @@ use std::{
   os::unix::net::UnixStream,
 };
 
-use n16_ipc::{Reply, Request};
+use n16_ipc::{Reply, Request, Response};
 
 pub fn send_request(request: &Request) -> io::Result<Reply> {
   let mut stream = UnixStream::connect(n16_ipc::socket_path())?;
@@ -26,6 +26,55 @@ pub fn send_request(request: &Request) -> io::Result<Reply> {
   Ok(reply)
 }
 
+/// Send `request` and pick the expected response with `expected`, which gives back any other
+/// response. Errors and unexpected responses are printed, returning `None`.
+pub fn request_response<T>(
+  request: &Request,
+  expected: impl FnOnce(Response) -> Result<T, Response>,
+) -> Option<T> {
+  match send_request(request) {
+    Ok(Ok(response)) => match expected(response) {
+      Ok(value) => Some(value),
+      Err(response) => {
+        eprintln!("Unexpected response from daemon: {response:?}");
+        None
+      }
+    },
+    Ok(Err(error)) => {
+      eprintln!("{error}");
+      None
+    }
+    Err(error) => {
+      eprintln!("Failed to connect to the n16 daemon: {error}");
+      None
+    }
+  }
+}
+
+/// Send [`Request::EventStream`] and print every event line until the daemon disconnects
+pub fn stream_events() -> io::Result<()> {
+  let mut stream = UnixStream::connect(n16_ipc::socket_path())?;
+
+  let mut buf = serde_json::to_vec(&Request::EventStream)?;
+  buf.push(b'\n');
+
+  stream.write_all(&buf)?;
+
+  let mut lines = BufReader::new(stream).lines();
+
+  let reply: Reply = match lines.next() {
+    Some(line) => serde_json::from_str(&line?)?,
+    None => return Ok(()),
+  };
+  reply.map_err(io::Error::other)?;
+
+  for line in lines {
+    println!("{}", line?);
+  }
+
+  Ok(())
+}
+
 pub fn send_request_ok(request: &Request) {
   send_request(request).unwrap().unwrap();
 }
@@ -1,19 +1,44 @@
+use std::process::ExitCode;
+
 use clap::Parser;
 use cli::Cli;
 use ipc::send_request_ok;
 
 mod cli;
+mod dmenu;
+mod export;
 mod ipc;
-pub fn main() {
+pub fn main() -> ExitCode {
   let cli = Cli::parse();
 
   match cli.command {
     cli::Command::Launcher(launcher) => {
+      if let Some(dmenu) = launcher.dmenu() {
+        return dmenu::run(&launcher.request(), dmenu);
+      }
+
       send_request_ok(&launcher.request());
     }
 
     cli::Command::Bar(bar) => {
       send_request_ok(&bar.request());
     }
+
+    cli::Command::Theme(theme) => {
+      if let Some(export) = theme.export() {
+        return export::run(&theme.request(), export);
+      }
+
+      send_request_ok(&theme.request());
+    }
+
+    cli::Command::EventStream => {
+      if let Err(error) = ipc::stream_events() {
+        eprintln!("{error}");
+        return ExitCode::FAILURE;
+      }
+    }
   }
+
+  ExitCode::SUCCESS
 }
@@ -0,0 +1,189 @@
+use std::sync::LazyLock;
+
+/// Layout used when neither the bar nor the output configure any zone
+static DEFAULT_LAYOUT: LazyLock<BarLayout> = LazyLock::new(|| BarLayout {
+  left: None,
+  center: None,
+  right: Some(BarZone {
+    modules: vec![BarModuleConfig::Clock(ClockConfig::default())],
+  }),
+});
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, knus::Decode)]
+pub struct ClockConfig {
+  /// strftime format of the time
+  #[knus(property)]
+  format: Option<String>,
+}
+
+impl ClockConfig {
+  pub fn format(&self) -> &str {
+    self.format.as_deref().unwrap_or("%H:%M")
+  }
+}
+
+/// A module shown in the bar, written as a node named after the module
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BarModuleConfig {
+  Clock(ClockConfig),
+  /// A module this version doesn't know, kept so the rest of the config still loads
+  Unknown(String),
+}
+
+impl<S> knus::Decode<S> for BarModuleConfig
+where
+  S: knus::traits::ErrorSpan,
+{
+  fn decode_node(
+    node: &knus::ast::SpannedNode<S>,
+    ctx: &mut knus::decode::Context<S>,
+  ) -> Result<Self, knus::errors::DecodeError<S>> {
+    match &**node.node_name {
+      "clock" => ClockConfig::decode_node(node, ctx).map(Self::Clock),
+      name => Ok(Self::Unknown(name.to_string())),
+    }
+  }
+}
+
+/// Modules of one part of the bar, in order
+#[derive(Debug, Clone, Default, knus::Decode)]
+pub struct BarZone {
+  #[knus(children)]
+  modules: Vec<BarModuleConfig>,
+}
+
+/// Modules on the left, in the center and on the right of a bar
+#[derive(Debug, Clone, Default, knus::Decode)]
+pub struct BarLayout {
+  #[knus(child)]
+  left: Option<BarZone>,
+  #[knus(child)]
+  center: Option<BarZone>,
+  #[knus(child)]
+  right: Option<BarZone>,
+}
+
+impl BarLayout {
+  pub fn left(&self) -> &[BarModuleConfig] {
+    self.left.as_ref().map_or(&[], |zone| &zone.modules)
+  }
+
+  pub fn center(&self) -> &[BarModuleConfig] {
+    self.center.as_ref().map_or(&[], |zone| &zone.modules)
+  }
+
+  pub fn right(&self) -> &[BarModuleConfig] {
+    self.right.as_ref().map_or(&[], |zone| &zone.modules)
+  }
+
+  /// Whether no zone is configured
+  fn is_empty(&self) -> bool {
+    self.left.is_none() && self.center.is_none() && self.right.is_none()
+  }
+}
+
+/// Bar settings for a single output
+#[derive(Debug, Clone, knus::Decode)]
+pub struct BarOutputConfig {
+  /// Output name, such as `DP-1`
+  #[knus(argument)]
+  name: String,
+  /// Replaces the layout of the bar on this output when any zone is configured
+  #[knus(flatten(child))]
+  layout: BarLayout,
+}
+
+#[derive(Debug, Clone, Default, knus::Decode)]
+pub struct BarConfig {
+  /// Outputs to show the bar on. The bar is shown on every output when none are configured.
+  #[knus(children(name = "output"))]
+  outputs: Vec<BarOutputConfig>,
+  #[knus(flatten(child))]
+  layout: BarLayout,
+}
+
+impl BarConfig {
+  /// Whether a bar should be shown on the output named `output`
+  pub fn shows_output(&self, output: &str) -> bool {
+    self.outputs.is_empty() || self.output(output).is_some()
+  }
+
+  /// Layout of the bar on the output named `output`, or of a bar on an unknown output
+  pub fn layout(&self, output: Option<&str>) -> &BarLayout {
+    output
+      .and_then(|output| self.output(output))
+      .map(|output| &output.layout)
+      .into_iter()
+      .chain([&self.layout])
+      .find(|layout| !layout.is_empty())
+      .unwrap_or(&DEFAULT_LAYOUT)
+  }
+
+  fn output(&self, name: &str) -> Option<&BarOutputConfig> {
+    self.outputs.iter().rev().find(|output| output.name == name)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::config::Config;
+
+  #[test]
+  fn unknown_modules_keep_the_config() {
+    let config: Config = knus::parse(
+      "config.kdl",
+      r#"
+        theme "gruvbox-dark-hard"
+        bar { left { workspaces; }; center { window-title; }; right { tray; battery; clock format="%a %d %H:%M"; }; }
+      "#,
+    )
+    .unwrap();
+    let layout = config.bar().layout(None);
+
+    assert_eq!(
+      layout.left(),
+      [BarModuleConfig::Unknown("workspaces".to_string())]
+    );
+    assert_eq!(
+      layout.center(),
+      [BarModuleConfig::Unknown("window-title".to_string())]
+    );
+    assert_eq!(
+      layout.right(),
+      [
+        BarModuleConfig::Unknown("tray".to_string()),
+        BarModuleConfig::Unknown("battery".to_string()),
+        BarModuleConfig::Clock(ClockConfig {
+          format: Some("%a %d %H:%M".to_string())
+        }),
+      ]
+    );
+  }
+
+  #[test]
+  fn output_layout_replaces_bar_layout() {
+    let config: Config = knus::parse(
+      "config.kdl",
+      r#"
+        theme "gruvbox-dark-hard"
+        bar {
+          right {
+            clock
+          }
+          output "DP-1"
+          output "HDMI-A-1" {
+            left
+          }
+        }
+      "#,
+    )
+    .unwrap();
+    let bar = config.bar();
+
+    assert!(bar.shows_output("DP-1"));
+    assert!(!bar.shows_output("eDP-1"));
+    assert_eq!(bar.layout(Some("DP-1")).right().len(), 1);
+    assert!(bar.layout(Some("HDMI-A-1")).right().is_empty());
+  }
+}
@@ -1,13 +1,40 @@
-use crate::theme::Base16Theme;
+use crate::{
+  config::{BarConfig, LauncherConfig, StyleConfig, ThemeConfig},
+  theme::{Base16Theme, ColorScheme},
+};
 
 #[derive(Debug, Clone, Default, knus::Decode)]
 pub struct Config {
   #[knus(child)]
-  theme: Base16Theme,
+  theme: ThemeConfig,
+  #[knus(child, default)]
+  launcher: LauncherConfig,
+  #[knus(child, default)]
+  bar: BarConfig,
+  #[knus(child, default)]
+  style: StyleConfig,
 }
 
 impl Config {
-  pub fn theme(&self) -> &Base16Theme {
+  /// The theme for the color scheme preference, with the widget style values from the `style`
+  /// section
+  pub fn theme(&self, scheme: ColorScheme) -> Base16Theme {
+    self.theme.theme(scheme, self.style.theme_style())
+  }
+
+  pub fn themes(&self) -> &ThemeConfig {
     &self.theme
   }
+
+  pub fn style(&self) -> &StyleConfig {
+    &self.style
+  }
+
+  pub fn launcher(&self) -> &LauncherConfig {
+    &self.launcher
+  }
+
+  pub fn bar(&self) -> &BarConfig {
+    &self.bar
+  }
 }
@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+use crate::config::ProviderSettings;
+
+/// Time budget of a single calculation when not configured
+const DEFAULT_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// Value of a currency, in units per one unit of the base currency
+#[derive(Debug, Clone, knus::Decode)]
+pub struct ExchangeRate {
+  #[knus(node_name)]
+  currency: String,
+  #[knus(argument)]
+  rate: f64,
+}
+
+/// Fixed exchange rates used for currency conversions.
+/// Rates are never fetched from the network, so conversions work offline.
+#[derive(Debug, Clone, Default, knus::Decode)]
+pub struct ExchangeRates {
+  #[knus(property)]
+  base: Option<String>,
+  #[knus(children)]
+  rates: Vec<ExchangeRate>,
+}
+
+impl ExchangeRates {
+  /// The base currency all rates are relative to, `USD` by default
+  pub fn base(&self) -> &str {
+    self.base.as_deref().unwrap_or("USD")
+  }
+
+  /// Value of `currency` relative to the base currency, if known
+  pub fn rate(&self, currency: &str) -> Option<f64> {
+    if currency.eq_ignore_ascii_case(self.base()) {
+      return Some(1.0);
+    }
+
+    self
+      .rates
+      .iter()
+      .find(|rate| rate.currency.eq_ignore_ascii_case(currency))
+      .map(|rate| rate.rate)
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.rates.is_empty()
+  }
+}
+
+#[derive(Debug, Clone, Default, knus::Decode)]
+pub struct CalculatorConfig {
+  #[knus(flatten(child))]
+  settings: ProviderSettings,
+  #[knus(child, unwrap(argument))]
+  timeout_ms: Option<u64>,
+  #[knus(child, default)]
+  exchange_rates: ExchangeRates,
+}
+
+impl AsRef<ProviderSettings> for CalculatorConfig {
+  fn as_ref(&self) -> &ProviderSettings {
+    &self.settings
+  }
+}
+
+impl CalculatorConfig {
+  /// Time after which a calculation is interrupted
+  pub fn timeout(&self) -> Duration {
+    self
+      .timeout_ms
+      .map_or(DEFAULT_TIMEOUT, Duration::from_millis)
+  }
+
+  pub fn exchange_rates(&self) -> &ExchangeRates {
+    &self.exchange_rates
+  }
+}
@@ -0,0 +1,473 @@
+use std::{fmt::Display, str::FromStr};
+
+use iced::keyboard::{Key, Modifiers, key::Named};
+
+use crate::decode::decode_scalar_from_str;
+
+/// Action triggered by a launcher keybind
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LauncherAction {
+  Close,
+  RunSelected,
+  /// Run the selected match with the provider's alternative action
+  RunSelectedAlt,
+  SelectPrev,
+  SelectNext,
+  SelectPageUp,
+  SelectPageDown,
+  SelectFirst,
+  SelectLast,
+  SelectPrevSection,
+  SelectNextSection,
+  /// Run the nth shown match, starting from 0
+  QuickSelect(usize),
+}
+
+/// A key combined with the modifiers that must be held, written as `Ctrl+Shift+Tab`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keybind {
+  key: Key,
+  modifiers: Modifiers,
+}
+
+impl Keybind {
+  fn new(key: impl Into<Key>, modifiers: Modifiers) -> Self {
+    Self {
+      key: key.into(),
+      modifiers,
+    }
+  }
+
+  pub fn matches(&self, key: &Key, modifiers: Modifiers) -> bool {
+    modifiers_eq(self.modifiers, modifiers) && keys_eq(&self.key, key)
+  }
+}
+
+fn modifiers_eq(a: Modifiers, b: Modifiers) -> bool {
+  a.shift() == b.shift() && a.control() == b.control() && a.alt() == b.alt() && a.logo() == b.logo()
+}
+
+fn keys_eq(a: &Key, b: &Key) -> bool {
+  match (a.as_ref(), b.as_ref()) {
+    (Key::Character(a), Key::Character(b)) => a.to_lowercase() == b.to_lowercase(),
+    (a, b) => a == b,
+  }
+}
+
+fn parse_modifier(name: &str) -> Option<Modifiers> {
+  Some(match name.to_lowercase().as_str() {
+    "ctrl" | "control" => Modifiers::CTRL,
+    "shift" => Modifiers::SHIFT,
+    "alt" => Modifiers::ALT,
+    "super" | "mod" | "logo" | "meta" => Modifiers::LOGO,
+    _ => return None,
+  })
+}
+
+fn parse_key(name: &str) -> Option<Key> {
+  let named = match name.to_lowercase().as_str() {
+    "up" | "arrowup" => Named::ArrowUp,
+    "down" | "arrowdown" => Named::ArrowDown,
+    "left" | "arrowleft" => Named::ArrowLeft,
+    "right" | "arrowright" => Named::ArrowRight,
+    "tab" => Named::Tab,
+    "enter" | "return" => Named::Enter,
+    "escape" | "esc" => Named::Escape,
+    "space" => Named::Space,
+    "backspace" => Named::Backspace,
+    "delete" => Named::Delete,
+    "pageup" => Named::PageUp,
+    "pagedown" => Named::PageDown,
+    "home" => Named::Home,
+    "end" => Named::End,
+    "f1" => Named::F1,
+    "f2" => Named::F2,
+    "f3" => Named::F3,
+    "f4" => Named::F4,
+    "f5" => Named::F5,
+    "f6" => Named::F6,
+    "f7" => Named::F7,
+    "f8" => Named::F8,
+    "f9" => Named::F9,
+    "f10" => Named::F10,
+    "f11" => Named::F11,
+    "f12" => Named::F12,
+    _ => {
+      let mut chars = name.chars();
+      let (Some(char), None) = (chars.next(), chars.next()) else {
+        return None;
+      };
+
+      return Some(Key::Character(char.to_lowercase().to_string().into()));
+    }
+  };
+
+  Some(Key::Named(named))
+}
+
+/// Name of a key as written in keybinds, `None` for keys that can't be bound
+fn key_name(key: &Key) -> Option<String> {
+  let named = match key.as_ref() {
+    Key::Character(char) => return Some(char.to_uppercase()),
+    Key::Named(named) => named,
+    Key::Unidentified => return None,
+  };
+
+  Some(
+    match named {
+      Named::ArrowUp => "Up",
+      Named::ArrowDown => "Down",
+      Named::ArrowLeft => "Left",
+      Named::ArrowRight => "Right",
+      Named::Tab => "Tab",
+      Named::Enter => "Enter",
+      Named::Escape => "Escape",
+      Named::Space => "Space",
+      Named::Backspace => "Backspace",
+      Named::Delete => "Delete",
+      Named::PageUp => "PageUp",
+      Named::PageDown => "PageDown",
+      Named::Home => "Home",
+      Named::End => "End",
+      Named::F1 => "F1",
+      Named::F2 => "F2",
+      Named::F3 => "F3",
+      Named::F4 => "F4",
+      Named::F5 => "F5",
+      Named::F6 => "F6",
+      Named::F7 => "F7",
+      Named::F8 => "F8",
+      Named::F9 => "F9",
+      Named::F10 => "F10",
+      Named::F11 => "F11",
+      Named::F12 => "F12",
+      _ => return None,
+    }
+    .to_string(),
+  )
+}
+
+/// Written like it is parsed, such as `Ctrl+Shift+Tab`
+impl Display for Keybind {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let modifiers = [
+      (self.modifiers.control(), "Ctrl"),
+      (self.modifiers.shift(), "Shift"),
+      (self.modifiers.alt(), "Alt"),
+      (self.modifiers.logo(), "Super"),
+    ];
+
+    for (_, name) in modifiers.into_iter().filter(|(held, _)| *held) {
+      write!(f, "{name}+")?;
+    }
+
+    f.write_str(&key_name(&self.key).unwrap_or_else(|| format!("{:?}", self.key)))
+  }
+}
+
+#[derive(Debug)]
+pub struct ParseKeybindError(String);
+
+impl Display for ParseKeybindError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(&self.0)
+  }
+}
+
+impl std::error::Error for ParseKeybindError {}
+
+impl FromStr for Keybind {
+  type Err = ParseKeybindError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    // A trailing `+` is the key itself, as in `+` or `Ctrl++`
+    let (modifier_names, key_name) = match s.strip_suffix('+') {
+      Some(modifier_names) if modifier_names.is_empty() || modifier_names.ends_with('+') => {
+        (modifier_names, "+")
+      }
+      _ => s.rsplit_once('+').unwrap_or(("", s)),
+    };
+
+    let modifiers = parse_modifiers(modifier_names)?;
+    let key =
+      parse_key(key_name).ok_or_else(|| ParseKeybindError(format!("unknown key `{key_name}`")))?;
+
+    Ok(Self { key, modifiers })
+  }
+}
+
+fn parse_modifiers(s: &str) -> Result<Modifiers, ParseKeybindError> {
+  s.split('+')
+    .filter(|name| !name.is_empty())
+    .try_fold(Modifiers::empty(), |modifiers, name| {
+      parse_modifier(name)
+        .map(|modifier| modifiers | modifier)
+        .ok_or_else(|| ParseKeybindError(format!("unknown modifier `{name}`")))
+    })
+}
+
+/// Modifiers held together with a digit to quick select a match, written as `Ctrl+Alt`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuickSelectModifiers(Modifiers);
+
+impl FromStr for QuickSelectModifiers {
+  type Err = ParseKeybindError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    parse_modifiers(s).map(Self)
+  }
+}
+
+decode_scalar_from_str!(Keybind);
+decode_scalar_from_str!(QuickSelectModifiers);
+
+/// Keybinds used by the launcher window.
+///
+/// Each action can be bound to any number of keys. Setting an action replaces its default keys.
+#[derive(Debug, Clone, Default, knus::Decode)]
+pub struct Keybinds {
+  #[knus(child, unwrap(arguments))]
+  close: Option<Vec<Keybind>>,
+  #[knus(child, unwrap(arguments))]
+  run_selected: Option<Vec<Keybind>>,
+  #[knus(child, unwrap(arguments))]
+  run_selected_alt: Option<Vec<Keybind>>,
+  #[knus(child, unwrap(arguments))]
+  select_prev: Option<Vec<Keybind>>,
+  #[knus(child, unwrap(arguments))]
+  select_next: Option<Vec<Keybind>>,
+  #[knus(child, unwrap(arguments))]
+  page_up: Option<Vec<Keybind>>,
+  #[knus(child, unwrap(arguments))]
+  page_down: Option<Vec<Keybind>>,
+  #[knus(child, unwrap(arguments))]
+  select_first: Option<Vec<Keybind>>,
+  #[knus(child, unwrap(arguments))]
+  select_last: Option<Vec<Keybind>>,
+  #[knus(child, unwrap(arguments))]
+  prev_section: Option<Vec<Keybind>>,
+  #[knus(child, unwrap(arguments))]
+  next_section: Option<Vec<Keybind>>,
+  #[knus(child, unwrap(arguments))]
+  quick_select: Option<Vec<QuickSelectModifiers>>,
+}
+
+impl Keybinds {
+  fn bindings(&self) -> [(LauncherAction, Vec<Keybind>); 11] {
+    let none = Modifiers::empty();
+
+    let or_default =
+      |keybinds: &Option<Vec<Keybind>>, default: Vec<Keybind>| keybinds.clone().unwrap_or(default);
+
+    [
+      (
+        LauncherAction::Close,
+        or_default(&self.close, vec![Keybind::new(Named::Escape, none)]),
+      ),
+      (
+        LauncherAction::RunSelected,
+        or_default(&self.run_selected, vec![Keybind::new(Named::Enter, none)]),
+      ),
+      (
+        LauncherAction::RunSelectedAlt,
+        or_default(
+          &self.run_selected_alt,
+          vec![Keybind::new(Named::Enter, Modifiers::SHIFT)],
+        ),
+      ),
+      (
+        LauncherAction::SelectPrev,
+        or_default(
+          &self.select_prev,
+          vec![
+            Keybind::new(Named::ArrowUp, none),
+            Keybind::new(Named::Tab, Modifiers::SHIFT),
+            Keybind::new(Key::Character("p".into()), Modifiers::CTRL),
+            Keybind::new(Key::Character("k".into()), Modifiers::CTRL),
+          ],
+        ),
+      ),
+      (
+        LauncherAction::SelectNext,
+        or_default(
+          &self.select_next,
+          vec![
+            Keybind::new(Named::ArrowDown, none),
+            Keybind::new(Named::Tab, none),
+            Keybind::new(Key::Character("n".into()), Modifiers::CTRL),
+            Keybind::new(Key::Character("j".into()), Modifiers::CTRL),
+          ],
+        ),
+      ),
+      (
+        LauncherAction::SelectPageUp,
+        or_default(&self.page_up, vec![Keybind::new(Named::PageUp, none)]),
+      ),
+      (
+        LauncherAction::SelectPageDown,
+        or_default(&self.page_down, vec![Keybind::new(Named::PageDown, none)]),
+      ),
+      (
+        LauncherAction::SelectFirst,
+        or_default(&self.select_first, vec![Keybind::new(Named::Home, none)]),
+      ),
+      (
+        LauncherAction::SelectLast,
+        or_default(&self.select_last, vec![Keybind::new(Named::End, none)]),
+      ),
+      (
+        LauncherAction::SelectPrevSection,
+        or_default(
+          &self.prev_section,
+          vec![Keybind::new(Named::ArrowUp, Modifiers::CTRL)],
+        ),
+      ),
+      (
+        LauncherAction::SelectNextSection,
+        or_default(
+          &self.next_section,
+          vec![Keybind::new(Named::ArrowDown, Modifiers::CTRL)],
+        ),
+      ),
+    ]
+  }
+
+  /// Keys bound to `action`, there are none for quick select
+  pub fn keys(&self, action: LauncherAction) -> Vec<Keybind> {
+    self
+      .bindings()
+      .into_iter()
+      .find(|(bound, _)| *bound == action)
+      .map(|(_, keybinds)| keybinds)
+      .unwrap_or_default()
+  }
+
+  /// Find the action bound to a key press, if any
+  pub fn action(&self, key: &Key, modifiers: Modifiers) -> Option<LauncherAction> {
+    if let Some(action) = self.quick_select_action(key, modifiers) {
+      return Some(action);
+    }
+
+    self
+      .bindings()
+      .into_iter()
+      .find(|(_, keybinds)| keybinds.iter().any(|bind| bind.matches(key, modifiers)))
+      .map(|(action, _)| action)
+  }
+
+  fn quick_select_action(&self, key: &Key, modifiers: Modifiers) -> Option<LauncherAction> {
+    let Key::Character(char) = key.as_ref() else {
+      return None;
+    };
+
+    let digit: usize = char.parse().ok().filter(|digit| (1..=9).contains(digit))?;
+
+    let quick_select = self
+      .quick_select
+      .as_deref()
+      .unwrap_or(&[QuickSelectModifiers(Modifiers::CTRL)]);
+
+    quick_select
+      .iter()
+      .any(|quick_select| modifiers_eq(quick_select.0, modifiers))
+      .then_some(LauncherAction::QuickSelect(digit - 1))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse(s: &str) -> Keybind {
+    s.parse().unwrap()
+  }
+
+  fn error(s: &str) -> String {
+    s.parse::<Keybind>().unwrap_err().to_string()
+  }
+
+  #[test]
+  fn parses_modifiers() {
+    assert_eq!(
+      parse("Ctrl+Shift+Tab"),
+      Keybind::new(Named::Tab, Modifiers::CTRL | Modifiers::SHIFT)
+    );
+    assert_eq!(
+      parse("control+ALT+super+Up"),
+      Keybind::new(
+        Named::ArrowUp,
+        Modifiers::CTRL | Modifiers::ALT | Modifiers::LOGO
+      )
+    );
+    assert_eq!(parse("Tab"), Keybind::new(Named::Tab, Modifiers::empty()));
+  }
+
+  #[test]
+  fn parses_named_keys() {
+    assert_eq!(
+      parse("esc"),
+      Keybind::new(Named::Escape, Modifiers::empty())
+    );
+    assert_eq!(
+      parse("Return"),
+      Keybind::new(Named::Enter, Modifiers::empty())
+    );
+    assert_eq!(
+      parse("PageDown"),
+      Keybind::new(Named::PageDown, Modifiers::empty())
+    );
+    assert_eq!(parse("F12"), Keybind::new(Named::F12, Modifiers::empty()));
+  }
+
+  #[test]
+  fn characters_match_case_insensitively() {
+    let keybind = parse("Ctrl+K");
+
+    assert!(keybind.matches(&Key::Character("k".into()), Modifiers::CTRL));
+    assert!(keybind.matches(&Key::Character("K".into()), Modifiers::CTRL));
+    assert!(!keybind.matches(&Key::Character("k".into()), Modifiers::empty()));
+    assert!(!keybind.matches(&Key::Character("j".into()), Modifiers::CTRL));
+  }
+
+  #[test]
+  fn parses_plus_key() {
+    assert_eq!(
+      parse("+"),
+      Keybind::new(Key::Character("+".into()), Modifiers::empty())
+    );
+    assert_eq!(
+      parse("Ctrl++"),
+      Keybind::new(Key::Character("+".into()), Modifiers::CTRL)
+    );
+    assert_eq!(
+      parse("Ctrl+Shift++"),
+      Keybind::new(
+        Key::Character("+".into()),
+        Modifiers::CTRL | Modifiers::SHIFT
+      )
+    );
+  }
+
+  #[test]
+  fn keybinds_are_written_like_they_are_parsed() {
+    for keybind in [
+      "Enter",
+      "Ctrl+Shift+Tab",
+      "Alt+Super+F4",
+      "Ctrl+K",
+      "Ctrl++",
+    ] {
+      assert_eq!(parse(keybind).to_string(), keybind);
+    }
+
+    assert_eq!(parse("control+esc").to_string(), "Ctrl+Escape");
+  }
+
+  #[test]
+  fn reports_unknown_keys_and_modifiers() {
+    assert_eq!(error("Ctrl+"), "unknown key ``");
+    assert_eq!(error("Ctrl+Foo"), "unknown key `Foo`");
+    assert_eq!(error("Hyper+a"), "unknown modifier `Hyper`");
+    assert_eq!(error(""), "unknown key ``");
+  }
+}
@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+use crate::config::{
+  ApplicationsConfig, CalculatorConfig, Keybinds, PowerManagementConfig, ProviderConfig,
+};
+
+/// Time without typing before providers are queried, when not configured
+const DEFAULT_SEARCH_DEBOUNCE: Duration = Duration::from_millis(50);
+
+/// Time a provider may take to return its matches, when not configured
+const DEFAULT_PROVIDER_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Default, knus::Decode)]
+pub struct LauncherConfig {
+  #[knus(child, default)]
+  keybinds: Keybinds,
+  #[knus(child, unwrap(argument))]
+  search_debounce_ms: Option<u64>,
+  #[knus(child, unwrap(argument))]
+  provider_timeout_ms: Option<u64>,
+  #[knus(children(name = "provider"))]
+  providers: Vec<ProviderConfig>,
+}
+
+impl LauncherConfig {
+  pub fn keybinds(&self) -> &Keybinds {
+    &self.keybinds
+  }
+
+  /// Config of the provider that `config` picks, from the last `provider` node for it.
+  /// Providers without a node use their default config.
+  fn provider<C: Clone + Default>(&self, config: fn(&ProviderConfig) -> Option<&C>) -> C {
+    self
+      .providers
+      .iter()
+      .rev()
+      .find_map(config)
+      .cloned()
+      .unwrap_or_default()
+  }
+
+  pub fn applications(&self) -> ApplicationsConfig {
+    self.provider(|provider| match provider {
+      ProviderConfig::Applications(config) => Some(config),
+      _ => None,
+    })
+  }
+
+  pub fn calculator(&self) -> CalculatorConfig {
+    self.provider(|provider| match provider {
+      ProviderConfig::Calculator(config) => Some(config),
+      _ => None,
+    })
+  }
+
+  pub fn power_management(&self) -> PowerManagementConfig {
+    self.provider(|provider| match provider {
+      ProviderConfig::PowerManagement(config) => Some(config),
+      _ => None,
+    })
+  }
+
+  /// Time without typing before the search text is sent to the providers
+  pub fn search_debounce(&self) -> Duration {
+    self
+      .search_debounce_ms
+      .map_or(DEFAULT_SEARCH_DEBOUNCE, Duration::from_millis)
+  }
+
+  /// Time after which a provider's matches for the search text are skipped
+  pub fn provider_timeout(&self) -> Duration {
+    self
+      .provider_timeout_ms
+      .map_or(DEFAULT_PROVIDER_TIMEOUT, Duration::from_millis)
+  }
+}
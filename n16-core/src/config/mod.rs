@@ -1,8 +1,24 @@
 use std::{env, fs, path::PathBuf};
 
+pub use bar::*;
 pub use base_config::*;
-
+pub use calculator::*;
+pub use keybinds::*;
+pub use launcher::*;
+pub use power_management::*;
+pub use providers::*;
+pub use style::*;
+pub use theme::*;
+
+mod bar;
 mod base_config;
+mod calculator;
+mod keybinds;
+mod launcher;
+mod power_management;
+mod providers;
+mod style;
+mod theme;
 
 pub fn find_config_file() -> Option<PathBuf> {
   let mut config_dir = {
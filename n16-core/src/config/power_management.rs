@@ -0,0 +1,151 @@
+use crate::config::ProviderSettings;
+
+/// An entry of the power menu, written as `action "Shut Down" { ... }`
+#[derive(Debug, Clone, knus::Decode)]
+pub struct PowerAction {
+  #[knus(argument)]
+  title: String,
+  #[knus(child, unwrap(arguments))]
+  command: Vec<String>,
+  #[knus(child, unwrap(arguments), default)]
+  keywords: Vec<String>,
+  /// Whether running the action loses unsaved work, so it has to be confirmed
+  #[knus(child, unwrap(argument))]
+  destructive: Option<bool>,
+  /// logind method telling whether the action is available, such as `CanHibernate`
+  #[knus(child, unwrap(argument))]
+  logind_check: Option<String>,
+}
+
+impl PowerAction {
+  fn new(
+    title: &str,
+    command: &[&str],
+    keywords: &[&str],
+    destructive: bool,
+    logind_check: Option<&str>,
+  ) -> Self {
+    Self {
+      title: title.into(),
+      command: command.iter().map(|s| (*s).into()).collect(),
+      keywords: keywords.iter().map(|s| (*s).into()).collect(),
+      destructive: Some(destructive),
+      logind_check: logind_check.map(Into::into),
+    }
+  }
+
+  pub fn title(&self) -> &str {
+    &self.title
+  }
+
+  /// Program followed by its arguments
+  pub fn command(&self) -> &[String] {
+    &self.command
+  }
+
+  pub fn keywords(&self) -> &[String] {
+    &self.keywords
+  }
+
+  pub fn destructive(&self) -> bool {
+    self.destructive.unwrap_or(false)
+  }
+
+  pub fn logind_check(&self) -> Option<&str> {
+    self.logind_check.as_deref()
+  }
+}
+
+/// Actions shown when none are configured
+fn default_actions() -> Vec<PowerAction> {
+  vec![
+    PowerAction::new(
+      "Lock",
+      &["loginctl", "lock-session"],
+      &["screen"],
+      false,
+      None,
+    ),
+    PowerAction::new(
+      "Log Out",
+      &["niri", "msg", "action", "quit"],
+      &["logout", "exit", "session"],
+      true,
+      None,
+    ),
+    PowerAction::new(
+      "Shut Down",
+      &["systemctl", "poweroff"],
+      &["power", "off"],
+      true,
+      Some("CanPowerOff"),
+    ),
+    PowerAction::new(
+      "Reboot",
+      &["systemctl", "reboot"],
+      &["power", "restart"],
+      true,
+      Some("CanReboot"),
+    ),
+    PowerAction::new(
+      "Reboot to Firmware",
+      &["systemctl", "reboot", "--firmware-setup"],
+      &["power", "restart", "bios", "uefi"],
+      true,
+      Some("CanRebootToFirmwareSetup"),
+    ),
+    PowerAction::new(
+      "Sleep",
+      &["systemctl", "suspend"],
+      &["power", "suspend"],
+      false,
+      Some("CanSuspend"),
+    ),
+    PowerAction::new(
+      "Hibernate",
+      &["systemctl", "hibernate"],
+      &["power"],
+      false,
+      Some("CanHibernate"),
+    ),
+    PowerAction::new(
+      "Suspend then Hibernate",
+      &["systemctl", "suspend-then-hibernate"],
+      &["power", "sleep"],
+      false,
+      Some("CanSuspendThenHibernate"),
+    ),
+  ]
+}
+
+#[derive(Debug, Clone, Default, knus::Decode)]
+pub struct PowerManagementConfig {
+  #[knus(flatten(child))]
+  settings: ProviderSettings,
+  /// Ask for confirmation before running destructive actions
+  #[knus(child, unwrap(argument))]
+  confirm: Option<bool>,
+  #[knus(children(name = "action"))]
+  actions: Vec<PowerAction>,
+}
+
+impl AsRef<ProviderSettings> for PowerManagementConfig {
+  fn as_ref(&self) -> &ProviderSettings {
+    &self.settings
+  }
+}
+
+impl PowerManagementConfig {
+  pub fn confirm(&self) -> bool {
+    self.confirm.unwrap_or(true)
+  }
+
+  /// The configured actions, replacing the default ones if there are any
+  pub fn actions(&self) -> Vec<PowerAction> {
+    if self.actions.is_empty() {
+      default_actions()
+    } else {
+      self.actions.clone()
+    }
+  }
+}
@@ -0,0 +1,134 @@
+use crate::config::{CalculatorConfig, PowerManagementConfig};
+
+pub const APPLICATIONS_PROVIDER_ID: &str = "n16/applications";
+pub const CALCULATOR_PROVIDER_ID: &str = "n16/calculator";
+pub const POWER_MANAGEMENT_PROVIDER_ID: &str = "n16/power_management";
+
+/// Settings shared by every launcher provider
+#[derive(Debug, Clone, Default, knus::Decode)]
+pub struct ProviderSettings {
+  #[knus(child, unwrap(argument))]
+  enabled: Option<bool>,
+  #[knus(child, unwrap(argument))]
+  priority: Option<i64>,
+  #[knus(child, unwrap(argument))]
+  max_results: Option<usize>,
+  #[knus(child, unwrap(argument))]
+  name: Option<String>,
+}
+
+impl ProviderSettings {
+  /// Whether the provider is used, `true` by default
+  pub fn enabled(&self) -> bool {
+    self.enabled.unwrap_or(true)
+  }
+
+  /// Priority overriding the provider's default, higher appears first
+  pub fn priority(&self) -> Option<i64> {
+    self.priority
+  }
+
+  /// Maximum number of matches shown for the provider
+  pub fn max_results(&self) -> Option<usize> {
+    self.max_results
+  }
+
+  /// Name overriding the provider's default name
+  pub fn name(&self) -> Option<&str> {
+    self.name.as_deref()
+  }
+}
+
+impl AsRef<ProviderSettings> for ProviderSettings {
+  fn as_ref(&self) -> &ProviderSettings {
+    self
+  }
+}
+
+/// How applications are started by the launcher
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, knus::DecodeScalar)]
+pub enum LaunchBackend {
+  /// Spawn the application as a detached child process of the daemon
+  #[default]
+  Spawn,
+  /// Spawn the application, then move it into its own systemd user scope so it doesn't share the
+  /// daemon's cgroup. Falls back to a plain child process if systemd can't be reached.
+  SystemdScope,
+}
+
+#[derive(Debug, Clone, Default, knus::Decode)]
+pub struct ApplicationsConfig {
+  #[knus(flatten(child))]
+  settings: ProviderSettings,
+  #[knus(child, unwrap(argument))]
+  launch_backend: Option<LaunchBackend>,
+}
+
+impl ApplicationsConfig {
+  pub fn launch_backend(&self) -> LaunchBackend {
+    self.launch_backend.unwrap_or_default()
+  }
+}
+
+impl AsRef<ProviderSettings> for ApplicationsConfig {
+  fn as_ref(&self) -> &ProviderSettings {
+    &self.settings
+  }
+}
+
+/// Config of a built-in launcher provider, written as `provider "n16/applications" { ... }`
+#[derive(Debug, Clone)]
+pub enum ProviderConfig {
+  Applications(ApplicationsConfig),
+  Calculator(CalculatorConfig),
+  PowerManagement(PowerManagementConfig),
+}
+
+impl<S> knus::Decode<S> for ProviderConfig
+where
+  S: knus::traits::ErrorSpan,
+{
+  fn decode_node(
+    node: &knus::ast::SpannedNode<S>,
+    ctx: &mut knus::decode::Context<S>,
+  ) -> Result<Self, knus::errors::DecodeError<S>> {
+    let mut arguments = node.arguments.iter();
+
+    let Some(id_value) = arguments.next() else {
+      return Err(knus::errors::DecodeError::missing(
+        node,
+        "provider id argument is required",
+      ));
+    };
+
+    if let Some(extra) = arguments.next() {
+      return Err(knus::errors::DecodeError::unexpected(
+        &extra.literal,
+        "argument",
+        "unexpected argument",
+      ));
+    }
+
+    let id: String = knus::traits::DecodeScalar::decode(id_value, ctx)?;
+
+    // The typed configs only describe the children of the node
+    let mut settings_node = node.clone();
+    settings_node.arguments.clear();
+
+    Ok(match id.as_str() {
+      APPLICATIONS_PROVIDER_ID => {
+        Self::Applications(knus::Decode::decode_node(&settings_node, ctx)?)
+      }
+      CALCULATOR_PROVIDER_ID => Self::Calculator(knus::Decode::decode_node(&settings_node, ctx)?),
+      POWER_MANAGEMENT_PROVIDER_ID => {
+        Self::PowerManagement(knus::Decode::decode_node(&settings_node, ctx)?)
+      }
+      _ => {
+        return Err(knus::errors::DecodeError::conversion(
+          &id_value.literal,
+          format!("unknown provider `{id}`"),
+        ));
+      }
+    })
+  }
+}
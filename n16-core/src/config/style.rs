@@ -0,0 +1,122 @@
+use crate::theme::ThemeStyle;
+
+/// Screen edge the bar is attached to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, knus::DecodeScalar)]
+pub enum BarEdge {
+  Top,
+  #[default]
+  Bottom,
+}
+
+#[derive(Debug, Clone, Default, knus::Decode)]
+pub struct LauncherStyle {
+  #[knus(child, unwrap(argument))]
+  width: Option<u32>,
+  #[knus(child, unwrap(argument))]
+  height: Option<u32>,
+  /// Distance from the top of the screen
+  #[knus(child, unwrap(argument))]
+  margin_top: Option<i32>,
+  #[knus(child, unwrap(argument))]
+  padding: Option<u16>,
+  #[knus(child, unwrap(argument))]
+  icon_size: Option<u16>,
+}
+
+impl LauncherStyle {
+  pub fn width(&self) -> u32 {
+    self.width.unwrap_or(1000)
+  }
+
+  pub fn height(&self) -> u32 {
+    self.height.unwrap_or(600)
+  }
+
+  pub fn margin_top(&self) -> i32 {
+    self.margin_top.unwrap_or(200)
+  }
+
+  pub fn padding(&self) -> f32 {
+    self.padding.map_or(8.0, f32::from)
+  }
+
+  pub fn icon_size(&self) -> f32 {
+    self.icon_size.map_or(30.0, f32::from)
+  }
+}
+
+#[derive(Debug, Clone, Default, knus::Decode)]
+pub struct BarStyle {
+  #[knus(child, unwrap(argument))]
+  height: Option<u32>,
+  #[knus(child, unwrap(argument))]
+  edge: Option<BarEdge>,
+  #[knus(child, unwrap(argument))]
+  padding: Option<u16>,
+}
+
+impl BarStyle {
+  pub fn height(&self) -> u32 {
+    self.height.unwrap_or(30)
+  }
+
+  pub fn edge(&self) -> BarEdge {
+    self.edge.unwrap_or_default()
+  }
+
+  pub fn padding(&self) -> f32 {
+    self.padding.map_or(5.0, f32::from)
+  }
+}
+
+/// Visual settings besides the theme colors
+#[derive(Debug, Clone, Default, knus::Decode)]
+pub struct StyleConfig {
+  /// Font family name, the system sans-serif font by default
+  #[knus(child, unwrap(argument))]
+  font: Option<String>,
+  #[knus(child, unwrap(argument))]
+  font_size: Option<u16>,
+  #[knus(child, unwrap(argument))]
+  corner_radius: Option<u16>,
+  /// Opacity of window backgrounds, from 0 to 1
+  #[knus(child, unwrap(argument))]
+  background_opacity: Option<f32>,
+  #[knus(child, default)]
+  launcher: LauncherStyle,
+  #[knus(child, default)]
+  bar: BarStyle,
+}
+
+impl StyleConfig {
+  pub fn font(&self) -> Option<&str> {
+    self.font.as_deref()
+  }
+
+  /// Size of regular text, other text sizes are scaled relative to it
+  pub fn font_size(&self) -> f32 {
+    self.font_size.map_or(16.0, f32::from)
+  }
+
+  /// Style values used by the theme's widget styles
+  pub fn theme_style(&self) -> ThemeStyle {
+    ThemeStyle {
+      corner_radius: self
+        .corner_radius
+        .map_or(ThemeStyle::DEFAULT.corner_radius, f32::from),
+      background_opacity: self
+        .background_opacity
+        .map_or(ThemeStyle::DEFAULT.background_opacity, |opacity| {
+          opacity.clamp(0.0, 1.0)
+        }),
+    }
+  }
+
+  pub fn launcher(&self) -> &LauncherStyle {
+    &self.launcher
+  }
+
+  pub fn bar(&self) -> &BarStyle {
+    &self.bar
+  }
+}
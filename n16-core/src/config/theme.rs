@@ -0,0 +1,77 @@
+use crate::theme::{Base16Theme, ColorScheme, ThemeStyle};
+
+/// The configured theme, either a single theme or one for each color scheme
+#[derive(Debug, Clone, Default)]
+pub struct ThemeConfig {
+  /// Used with the dark preference and without a preference, or always for a single theme
+  dark: Base16Theme,
+  /// Used with the light preference, only set when separate light and dark themes are configured
+  light: Option<Base16Theme>,
+}
+
+impl ThemeConfig {
+  /// Theme used for the color scheme preference, with the given widget style values
+  pub fn theme(&self, scheme: ColorScheme, style: ThemeStyle) -> Base16Theme {
+    let theme = match &self.light {
+      Some(light) if scheme == ColorScheme::Light => light,
+      _ => &self.dark,
+    };
+
+    Base16Theme {
+      style,
+      ..theme.clone()
+    }
+  }
+}
+
+impl<S> knus::Decode<S> for ThemeConfig
+where
+  S: knus::traits::ErrorSpan,
+{
+  fn decode_node(
+    node: &knus::ast::SpannedNode<S>,
+    ctx: &mut knus::decode::Context<S>,
+  ) -> Result<Self, knus::errors::DecodeError<S>> {
+    let children = || node.children.iter().flat_map(|children| children.iter());
+    let is_variant =
+      |child: &knus::ast::SpannedNode<S>| matches!(&**child.node_name, "light" | "dark");
+
+    if !children().any(is_variant) {
+      return Ok(Self {
+        dark: Base16Theme::decode_node(node, ctx)?,
+        light: None,
+      });
+    }
+
+    // `theme { light ..; dark .. }`, each variant is written like a single theme node
+    let mut light = None;
+    let mut dark = None;
+
+    for child in children() {
+      match &**child.node_name {
+        "light" => light = Some(Base16Theme::decode_node(child, ctx)?),
+        "dark" => dark = Some(Base16Theme::decode_node(child, ctx)?),
+        name => ctx.emit_error(knus::errors::DecodeError::unexpected(
+          &child.node_name,
+          "node",
+          format!("unexpected node `{name}` next to the `light` and `dark` themes"),
+        )),
+      }
+    }
+
+    match (light, dark) {
+      (Some(light), Some(dark)) => Ok(Self {
+        dark,
+        light: Some(light),
+      }),
+      (None, _) => Err(knus::errors::DecodeError::missing(
+        node,
+        "the `light` theme is missing",
+      )),
+      (_, None) => Err(knus::errors::DecodeError::missing(
+        node,
+        "the `dark` theme is missing",
+      )),
+    }
+  }
+}
@@ -0,0 +1,40 @@
+/// Implement `knus::DecodeScalar` for a type that is parsed from a string with its `FromStr` impl.
+/// Parse errors are reported as conversion errors of the string value.
+macro_rules! decode_scalar_from_str {
+  ($type:ty) => {
+    impl<S> knus::DecodeScalar<S> for $type
+    where
+      S: knus::traits::ErrorSpan,
+    {
+      fn type_check(
+        type_name: &Option<knus::span::Spanned<knus::ast::TypeName, S>>,
+        ctx: &mut knus::decode::Context<S>,
+      ) {
+        if let Some(typ) = type_name {
+          ctx.emit_error(knus::errors::DecodeError::TypeName {
+            span: typ.span().clone(),
+            found: Some((**typ).clone()),
+            expected: knus::errors::ExpectedType::no_type(),
+            rust_type: stringify!($type),
+          });
+        }
+      }
+
+      fn raw_decode(
+        value: &knus::span::Spanned<knus::ast::Literal, S>,
+        _ctx: &mut knus::decode::Context<S>,
+      ) -> Result<Self, knus::errors::DecodeError<S>> {
+        match &**value {
+          knus::ast::Literal::String(s) => <$type as std::str::FromStr>::from_str(s)
+            .map_err(|error| knus::errors::DecodeError::conversion(value, error)),
+          _ => Err(knus::errors::DecodeError::scalar_kind(
+            knus::decode::Kind::String,
+            value,
+          )),
+        }
+      }
+    }
+  };
+}
+
+pub(crate) use decode_scalar_from_str;
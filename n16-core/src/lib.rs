@@ -1,2 +1,3 @@
 pub mod config;
+mod decode;
 pub mod theme;
@@ -3,18 +3,28 @@ use iced::theme::{Base, Style};
 use crate::theme::Base16Theme;
 
 impl Base for Base16Theme {
-  fn default(_preference: iced::theme::Mode) -> Self {
-    crate::theme::DEFAULT_THEME
+  fn default(preference: iced::theme::Mode) -> Self {
+    match preference {
+      iced::theme::Mode::Light => crate::theme::DEFAULT_LIGHT_THEME,
+      iced::theme::Mode::Dark | iced::theme::Mode::None => crate::theme::DEFAULT_THEME,
+    }
   }
 
   fn mode(&self) -> iced::theme::Mode {
-    iced::theme::Mode::None
+    let background = self.background();
+    let luminance = 0.2126 * background.r + 0.7152 * background.g + 0.0722 * background.b;
+
+    if luminance > 0.5 {
+      iced::theme::Mode::Light
+    } else {
+      iced::theme::Mode::Dark
+    }
   }
 
   fn base(&self) -> Style {
     Style {
-      background_color: self.base00,
-      text_color: self.base05,
+      background_color: self.background().scale_alpha(self.style.background_opacity),
+      text_color: self.foreground(),
     }
   }
 
@@ -26,3 +36,26 @@ impl Base for Base16Theme {
     "Base16 Theme"
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use iced::theme::{Base, Mode};
+
+  use crate::theme::Base16Theme;
+
+  #[test]
+  fn default_follows_preference() {
+    assert_eq!(
+      <Base16Theme as Base>::default(Mode::Light).mode(),
+      Mode::Light
+    );
+    assert_eq!(
+      <Base16Theme as Base>::default(Mode::Dark).mode(),
+      Mode::Dark
+    );
+    assert_eq!(
+      <Base16Theme as Base>::default(Mode::None).mode(),
+      Mode::Dark
+    );
+  }
+}
@@ -12,7 +12,7 @@ impl Catalog for Base16Theme {
       match status {
         Status::Active | Status::Pressed => base,
         Status::Hovered => Style {
-          background: Some(theme.base02.into()),
+          background: Some(theme.selection().into()),
           ..base
         },
         Status::Disabled => disabled(base),
@@ -27,9 +27,9 @@ impl Catalog for Base16Theme {
 
 pub fn base(theme: &Base16Theme) -> Style {
   Style {
-    background: Some(theme.base01.into()),
-    text_color: theme.base05,
-    border: border::rounded(2),
+    background: Some(theme.surface().into()),
+    text_color: theme.foreground(),
+    border: border::rounded(theme.style.corner_radius),
     ..Style::default()
   }
 }
@@ -27,5 +27,5 @@ pub fn colored(color: Color) -> Style {
 }
 
 pub fn base(theme: &Base16Theme) -> Style {
-  colored(theme.base03)
+  colored(theme.border())
 }
@@ -11,24 +11,24 @@ impl Catalog for Base16Theme {
     Box::new(|theme, _status| {
       let scrollbar = Rail {
         background: None,
-        border: border::rounded(2),
+        border: border::rounded(theme.style.corner_radius),
         scroller: Scroller {
           background: Background::Color(Color::default()),
-          border: border::rounded(2),
+          border: border::rounded(theme.style.corner_radius),
         },
       };
 
       let auto_scroll = AutoScroll {
-        background: theme.base00.scale_alpha(0.9).into(),
+        background: theme.background_dark().scale_alpha(0.9).into(),
         border: border::rounded(u32::MAX)
           .width(1)
-          .color(theme.base05.scale_alpha(0.8)),
+          .color(theme.foreground().scale_alpha(0.8)),
         shadow: Shadow {
           color: Color::BLACK.scale_alpha(0.7),
           offset: Vector::ZERO,
           blur_radius: 2.0,
         },
-        icon: theme.base05.scale_alpha(0.8),
+        icon: theme.foreground().scale_alpha(0.8),
       };
 
       Style {
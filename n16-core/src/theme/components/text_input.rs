@@ -17,16 +17,16 @@ impl Catalog for Base16Theme {
 
 pub fn base(theme: &Base16Theme, status: Status) -> Style {
   let active = Style {
-    background: theme.base00.into(),
+    background: theme.background_dark().into(),
     border: Border {
-      radius: 2.0.into(),
+      radius: theme.style.corner_radius.into(),
       width: 0.0,
-      color: theme.base00,
+      color: theme.background_dark(),
     },
-    icon: theme.base06,
-    placeholder: theme.base03,
-    value: theme.base05,
-    selection: theme.base02,
+    icon: theme.foreground(),
+    placeholder: theme.foreground_muted(),
+    value: theme.foreground(),
+    selection: theme.selection(),
   };
 
   match status {
@@ -36,7 +36,7 @@ pub fn base(theme: &Base16Theme, status: Status) -> Style {
       ..active
     },
     Status::Disabled => Style {
-      background: theme.base01.into(),
+      background: theme.surface().into(),
       value: active.placeholder,
       ..active
     },
@@ -1,8 +1,13 @@
 use iced::{Color, color};
 
 pub use components::*;
+pub use roles::*;
+pub use scheme::*;
 
 mod components;
+mod palette;
+mod roles;
+mod scheme;
 mod serialize;
 
 #[expect(non_snake_case, reason = "struct fields follow base16 naming scheme")]
@@ -24,6 +29,53 @@ pub struct Base16Theme {
   pub base0D: Color,
   pub base0E: Color,
   pub base0F: Color,
+
+  // Base24 extension. Schemes without it use base00 for the backgrounds and the matching base16
+  // accent for the bright accents, as suggested by the base24 spec.
+  /// Darker background
+  pub base10: Color,
+  /// Darkest background
+  pub base11: Color,
+  /// Bright red
+  pub base12: Color,
+  /// Bright yellow
+  pub base13: Color,
+  /// Bright green
+  pub base14: Color,
+  /// Bright cyan
+  pub base15: Color,
+  /// Bright blue
+  pub base16: Color,
+  /// Bright magenta
+  pub base17: Color,
+
+  pub roles: ColorRoles,
+  pub style: ThemeStyle,
+}
+
+/// Style values used by the widget styles besides the colors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThemeStyle {
+  pub corner_radius: f32,
+  /// Opacity of window backgrounds, from 0 to 1
+  pub background_opacity: f32,
+}
+
+impl ThemeStyle {
+  pub const DEFAULT: Self = Self {
+    corner_radius: 2.0,
+    background_opacity: 1.0,
+  };
+}
+
+/// Light or dark preference, as reported by the `org.freedesktop.appearance color-scheme`
+/// portal setting
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorScheme {
+  #[default]
+  NoPreference,
+  Dark,
+  Light,
 }
 
 #[expect(clippy::unreadable_literal, reason = "Hex codes")]
@@ -44,6 +96,51 @@ pub const DEFAULT_THEME: Base16Theme = Base16Theme {
   base0D: color!(0x81a2be),
   base0E: color!(0xb294bb),
   base0F: color!(0xa3685a),
+
+  base10: color!(0x1d1f21),
+  base11: color!(0x1d1f21),
+  base12: color!(0xcc6666),
+  base13: color!(0xf0c674),
+  base14: color!(0xb5bd68),
+  base15: color!(0x8abeb7),
+  base16: color!(0x81a2be),
+  base17: color!(0xb294bb),
+
+  roles: ColorRoles::DEFAULT,
+  style: ThemeStyle::DEFAULT,
+};
+
+/// Light counterpart of [`DEFAULT_THEME`], used when the light color scheme is preferred
+#[expect(clippy::unreadable_literal, reason = "Hex codes")]
+pub const DEFAULT_LIGHT_THEME: Base16Theme = Base16Theme {
+  base00: color!(0xffffff),
+  base01: color!(0xe0e0e0),
+  base02: color!(0xd6d6d6),
+  base03: color!(0x8e908c),
+  base04: color!(0x969896),
+  base05: color!(0x4d4d4c),
+  base06: color!(0x282a2e),
+  base07: color!(0x1d1f21),
+  base08: color!(0xc82829),
+  base09: color!(0xf5871f),
+  base0A: color!(0xeab700),
+  base0B: color!(0x718c00),
+  base0C: color!(0x3e999f),
+  base0D: color!(0x4271ae),
+  base0E: color!(0x8959a8),
+  base0F: color!(0xa3685a),
+
+  base10: color!(0xffffff),
+  base11: color!(0xffffff),
+  base12: color!(0xc82829),
+  base13: color!(0xeab700),
+  base14: color!(0x718c00),
+  base15: color!(0x3e999f),
+  base16: color!(0x4271ae),
+  base17: color!(0x8959a8),
+
+  roles: ColorRoles::DEFAULT,
+  style: ThemeStyle::DEFAULT,
 };
 
 impl Default for Base16Theme {
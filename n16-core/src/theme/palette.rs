@@ -0,0 +1,27 @@
+use iced::Color;
+use n16_ipc::theme::Palette;
+
+use crate::theme::{Base16Theme, Slot};
+
+fn hex(color: Color) -> String {
+  let [r, g, b, _] = color.into_rgba8();
+  format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+impl Base16Theme {
+  /// Colors of the theme as sent over IPC
+  pub fn palette(&self) -> Palette {
+    Palette {
+      colors: Slot::ALL
+        .iter()
+        .map(|slot| (slot.name(), hex(self.color(*slot))))
+        .collect(),
+      roles: self
+        .roles
+        .named()
+        .iter()
+        .map(|(name, slot)| ((*name).to_string(), hex(self.color(*slot))))
+        .collect(),
+    }
+  }
+}
@@ -0,0 +1,226 @@
+use std::str::FromStr;
+
+use iced::Color;
+
+use crate::theme::Base16Theme;
+
+/// A palette slot, `base00` through `base17`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slot {
+  Base00,
+  Base01,
+  Base02,
+  Base03,
+  Base04,
+  Base05,
+  Base06,
+  Base07,
+  Base08,
+  Base09,
+  Base0A,
+  Base0B,
+  Base0C,
+  Base0D,
+  Base0E,
+  Base0F,
+  Base10,
+  Base11,
+  Base12,
+  Base13,
+  Base14,
+  Base15,
+  Base16,
+  Base17,
+}
+
+impl Slot {
+  pub const ALL: [Self; 24] = [
+    Self::Base00,
+    Self::Base01,
+    Self::Base02,
+    Self::Base03,
+    Self::Base04,
+    Self::Base05,
+    Self::Base06,
+    Self::Base07,
+    Self::Base08,
+    Self::Base09,
+    Self::Base0A,
+    Self::Base0B,
+    Self::Base0C,
+    Self::Base0D,
+    Self::Base0E,
+    Self::Base0F,
+    Self::Base10,
+    Self::Base11,
+    Self::Base12,
+    Self::Base13,
+    Self::Base14,
+    Self::Base15,
+    Self::Base16,
+    Self::Base17,
+  ];
+
+  /// Name of the slot, such as `base0D`
+  pub fn name(self) -> String {
+    format!("base{:02X}", self as u8)
+  }
+}
+
+impl FromStr for Slot {
+  type Err = String;
+
+  /// Parse a slot name such as `base0D`, ignoring case
+  fn from_str(name: &str) -> Result<Self, Self::Err> {
+    let digits = name
+      .get(..4)
+      .filter(|prefix| prefix.eq_ignore_ascii_case("base"))
+      .and_then(|_| name.get(4..))
+      .filter(|digits| digits.len() == 2 && digits.chars().all(|c| c.is_ascii_hexdigit()));
+
+    digits
+      .and_then(|digits| usize::from_str_radix(digits, 16).ok())
+      .and_then(|index| Self::ALL.get(index).copied())
+      .ok_or_else(|| format!("unknown palette slot `{name}`, expected base00 through base17"))
+  }
+}
+
+/// Palette slots used for each part of the interface.
+/// The defaults follow the base16 styling guidelines, using the base24 colors where they apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColorRoles {
+  /// Window background
+  pub background: Slot,
+  /// Darker background of inputs
+  pub background_dark: Slot,
+  /// Background of buttons and other raised elements
+  pub surface: Slot,
+  /// Background of selected and hovered elements
+  pub selection: Slot,
+  /// Separators and outlines
+  pub border: Slot,
+  /// Regular text
+  pub foreground: Slot,
+  /// Descriptions, placeholders and other secondary text
+  pub foreground_muted: Slot,
+  pub accent: Slot,
+  pub accent_secondary: Slot,
+  pub error: Slot,
+  pub warning: Slot,
+}
+
+impl ColorRoles {
+  pub const DEFAULT: Self = Self {
+    background: Slot::Base00,
+    background_dark: Slot::Base10,
+    surface: Slot::Base01,
+    selection: Slot::Base02,
+    border: Slot::Base03,
+    foreground: Slot::Base05,
+    foreground_muted: Slot::Base04,
+    accent: Slot::Base0D,
+    accent_secondary: Slot::Base0E,
+    error: Slot::Base12,
+    warning: Slot::Base13,
+  };
+}
+
+impl ColorRoles {
+  /// Every role with its name as written in the config, such as `accent-secondary`
+  pub fn named(&self) -> [(&'static str, Slot); 11] {
+    [
+      ("background", self.background),
+      ("background-dark", self.background_dark),
+      ("surface", self.surface),
+      ("selection", self.selection),
+      ("border", self.border),
+      ("foreground", self.foreground),
+      ("foreground-muted", self.foreground_muted),
+      ("accent", self.accent),
+      ("accent-secondary", self.accent_secondary),
+      ("error", self.error),
+      ("warning", self.warning),
+    ]
+  }
+}
+
+impl Default for ColorRoles {
+  fn default() -> Self {
+    Self::DEFAULT
+  }
+}
+
+impl Base16Theme {
+  pub fn color(&self, slot: Slot) -> Color {
+    match slot {
+      Slot::Base00 => self.base00,
+      Slot::Base01 => self.base01,
+      Slot::Base02 => self.base02,
+      Slot::Base03 => self.base03,
+      Slot::Base04 => self.base04,
+      Slot::Base05 => self.base05,
+      Slot::Base06 => self.base06,
+      Slot::Base07 => self.base07,
+      Slot::Base08 => self.base08,
+      Slot::Base09 => self.base09,
+      Slot::Base0A => self.base0A,
+      Slot::Base0B => self.base0B,
+      Slot::Base0C => self.base0C,
+      Slot::Base0D => self.base0D,
+      Slot::Base0E => self.base0E,
+      Slot::Base0F => self.base0F,
+      Slot::Base10 => self.base10,
+      Slot::Base11 => self.base11,
+      Slot::Base12 => self.base12,
+      Slot::Base13 => self.base13,
+      Slot::Base14 => self.base14,
+      Slot::Base15 => self.base15,
+      Slot::Base16 => self.base16,
+      Slot::Base17 => self.base17,
+    }
+  }
+
+  pub fn background(&self) -> Color {
+    self.color(self.roles.background)
+  }
+
+  pub fn background_dark(&self) -> Color {
+    self.color(self.roles.background_dark)
+  }
+
+  pub fn surface(&self) -> Color {
+    self.color(self.roles.surface)
+  }
+
+  pub fn selection(&self) -> Color {
+    self.color(self.roles.selection)
+  }
+
+  pub fn border(&self) -> Color {
+    self.color(self.roles.border)
+  }
+
+  pub fn foreground(&self) -> Color {
+    self.color(self.roles.foreground)
+  }
+
+  pub fn foreground_muted(&self) -> Color {
+    self.color(self.roles.foreground_muted)
+  }
+
+  pub fn accent(&self) -> Color {
+    self.color(self.roles.accent)
+  }
+
+  pub fn accent_secondary(&self) -> Color {
+    self.color(self.roles.accent_secondary)
+  }
+
+  pub fn error(&self) -> Color {
+    self.color(self.roles.error)
+  }
+
+  pub fn warning(&self) -> Color {
+    self.color(self.roles.warning)
+  }
+}
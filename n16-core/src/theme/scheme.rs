@@ -0,0 +1,204 @@
+use std::{fs, path::Path, str::FromStr};
+
+use iced::Color;
+use yaml_rust2::{Yaml, YamlLoader, yaml::Hash};
+
+use crate::theme::{Base16Theme, ColorRoles, ThemeStyle};
+
+/// Schemes compiled into n16, selected by name with `theme "<name>"`
+const BUNDLED_SCHEMES: &[(&str, &str)] = &[
+  (
+    "catppuccin-latte",
+    include_str!("schemes/catppuccin-latte.yaml"),
+  ),
+  (
+    "catppuccin-mocha",
+    include_str!("schemes/catppuccin-mocha.yaml"),
+  ),
+  ("dracula", include_str!("schemes/dracula.yaml")),
+  (
+    "gruvbox-dark-hard",
+    include_str!("schemes/gruvbox-dark-hard.yaml"),
+  ),
+  (
+    "gruvbox-dark-medium",
+    include_str!("schemes/gruvbox-dark-medium.yaml"),
+  ),
+  (
+    "gruvbox-light-hard",
+    include_str!("schemes/gruvbox-light-hard.yaml"),
+  ),
+  ("nord", include_str!("schemes/nord.yaml")),
+  ("onedark", include_str!("schemes/onedark.yaml")),
+  ("rose-pine", include_str!("schemes/rose-pine.yaml")),
+  (
+    "solarized-dark",
+    include_str!("schemes/solarized-dark.yaml"),
+  ),
+  (
+    "solarized-light",
+    include_str!("schemes/solarized-light.yaml"),
+  ),
+  (
+    "tokyo-night-dark",
+    include_str!("schemes/tokyo-night-dark.yaml"),
+  ),
+  (
+    "tomorrow-night",
+    include_str!("schemes/tomorrow-night.yaml"),
+  ),
+];
+
+/// Names of the bundled schemes
+pub fn bundled_scheme_names() -> impl Iterator<Item = &'static str> {
+  BUNDLED_SCHEMES.iter().map(|(name, _)| *name)
+}
+
+/// Load a bundled scheme by name
+pub fn bundled_scheme(name: &str) -> Option<Base16Theme> {
+  let (_, text) = BUNDLED_SCHEMES.iter().find(|(n, _)| *n == name)?;
+
+  Some(parse_scheme(text).expect("bundled schemes are valid"))
+}
+
+/// Parse a base16 or base24 scheme in YAML or JSON.
+///
+/// Both the tinted-theming format with a `palette` mapping and the older flat format with the
+/// `baseXX` keys at the top level are supported, which also covers Stylix's `palette.json`.
+#[expect(clippy::similar_names, reason = "Names follow the base16 color names")]
+pub fn parse_scheme(text: &str) -> Result<Base16Theme, String> {
+  // YAML is a superset of JSON, so JSON schemes are parsed by the same parser
+  let documents = YamlLoader::load_from_str(text).map_err(|error| error.to_string())?;
+
+  let Some(root) = documents.first().and_then(Yaml::as_hash) else {
+    return Err("expected a mapping of base16 colors".into());
+  };
+
+  let palette = root
+    .get(&Yaml::String("palette".into()))
+    .and_then(Yaml::as_hash)
+    .unwrap_or(root);
+
+  let color =
+    |key: &str| palette_color(palette, key)?.ok_or_else(|| format!("missing color {key}"));
+  let base24_color =
+    |key: &str, fallback: Color| Ok::<_, String>(palette_color(palette, key)?.unwrap_or(fallback));
+
+  let base00 = color("base00")?;
+  let base08 = color("base08")?;
+  let base0a = color("base0A")?;
+  let base0b = color("base0B")?;
+  let base0c = color("base0C")?;
+  let base0d = color("base0D")?;
+  let base0e = color("base0E")?;
+
+  Ok(Base16Theme {
+    base00,
+    base01: color("base01")?,
+    base02: color("base02")?,
+    base03: color("base03")?,
+    base04: color("base04")?,
+    base05: color("base05")?,
+    base06: color("base06")?,
+    base07: color("base07")?,
+    base08,
+    base09: color("base09")?,
+    base0A: base0a,
+    base0B: base0b,
+    base0C: base0c,
+    base0D: base0d,
+    base0E: base0e,
+    base0F: color("base0F")?,
+
+    base10: base24_color("base10", base00)?,
+    base11: base24_color("base11", base00)?,
+    base12: base24_color("base12", base08)?,
+    base13: base24_color("base13", base0a)?,
+    base14: base24_color("base14", base0b)?,
+    base15: base24_color("base15", base0c)?,
+    base16: base24_color("base16", base0d)?,
+    base17: base24_color("base17", base0e)?,
+
+    roles: ColorRoles::DEFAULT,
+    style: ThemeStyle::DEFAULT,
+  })
+}
+
+/// Color of `key` in the palette if it is defined, looked up case insensitively since schemes use
+/// both `base0A` and `base0a`
+fn palette_color(palette: &Hash, key: &str) -> Result<Option<Color>, String> {
+  let Some((_, value)) = palette
+    .iter()
+    .find(|(k, _)| k.as_str().is_some_and(|k| k.eq_ignore_ascii_case(key)))
+  else {
+    return Ok(None);
+  };
+
+  let hex = value
+    .as_str()
+    .ok_or_else(|| format!("{key} is not a hex color string"))?;
+
+  Color::from_str(hex)
+    .map(Some)
+    .map_err(|_| format!("{key} is not a valid hex color: {hex}"))
+}
+
+/// Load a base16 scheme file in YAML or JSON. A leading `~/` is expanded to the home directory.
+pub fn load_scheme_file(path: &str) -> Result<Base16Theme, String> {
+  let path = match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+    (Some(relative), Some(home)) => Path::new(&home).join(relative),
+    _ => Path::new(path).to_path_buf(),
+  };
+
+  let text = fs::read_to_string(&path)
+    .map_err(|error| format!("failed to read {}: {error}", path.display()))?;
+
+  parse_scheme(&text).map_err(|error| format!("invalid scheme {}: {error}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const COLORS: [&str; 16] = [
+    "1e1e2e", "181825", "313244", "45475a", "585b70", "cdd6f4", "f5e0dc", "b4befe", "f38ba8",
+    "fab387", "f9e2af", "a6e3a1", "94e2d5", "89b4fa", "cba6f7", "f2cdcd",
+  ];
+
+  fn color(hex: &str) -> Color {
+    Color::from_str(hex).unwrap()
+  }
+
+  #[test]
+  fn every_bundled_scheme_parses() {
+    for name in bundled_scheme_names() {
+      assert!(bundled_scheme(name).is_some(), "{name}");
+    }
+  }
+
+  #[test]
+  fn parses_flat_json_palette() {
+    let entries: Vec<String> = COLORS
+      .iter()
+      .enumerate()
+      .map(|(idx, hex)| format!(r#""base{idx:02X}": "{hex}""#))
+      .collect();
+    let json = format!("{{{}}}", entries.join(", "));
+
+    let theme = parse_scheme(&json).unwrap();
+
+    assert_eq!(theme.base00, color("1e1e2e"));
+    assert_eq!(theme.base0F, color("f2cdcd"));
+    // base24 colors fall back to their base16 counterparts
+    assert_eq!(theme.base12, theme.base08);
+  }
+
+  #[test]
+  fn reports_invalid_colors() {
+    let error = parse_scheme("palette:\n  base00: \"not a color\"\n").unwrap_err();
+    assert_eq!(error, "base00 is not a valid hex color: not a color");
+
+    let error = parse_scheme("palette:\n  base00: \"#1e1e2e\"\n").unwrap_err();
+    assert_eq!(error, "missing color base08");
+  }
+}
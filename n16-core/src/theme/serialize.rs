@@ -1,7 +1,13 @@
 use iced::Color;
 use std::str::FromStr;
 
-use crate::theme::Base16Theme;
+use crate::{
+  decode::decode_scalar_from_str,
+  theme::{
+    Base16Theme, ColorRoles, Slot, ThemeStyle, bundled_scheme, bundled_scheme_names,
+    load_scheme_file,
+  },
+};
 
 #[derive(Debug, Clone)]
 struct HexColor(Color);
@@ -42,6 +48,55 @@ where
   }
 }
 
+decode_scalar_from_str!(Slot);
+
+/// Remapped color roles, roles that aren't set keep their default slot
+#[derive(Debug, Clone, Default, knus::Decode)]
+struct RolesRepr {
+  #[knus(child, unwrap(argument))]
+  background: Option<Slot>,
+  #[knus(child, unwrap(argument))]
+  background_dark: Option<Slot>,
+  #[knus(child, unwrap(argument))]
+  surface: Option<Slot>,
+  #[knus(child, unwrap(argument))]
+  selection: Option<Slot>,
+  #[knus(child, unwrap(argument))]
+  border: Option<Slot>,
+  #[knus(child, unwrap(argument))]
+  foreground: Option<Slot>,
+  #[knus(child, unwrap(argument))]
+  foreground_muted: Option<Slot>,
+  #[knus(child, unwrap(argument))]
+  accent: Option<Slot>,
+  #[knus(child, unwrap(argument))]
+  accent_secondary: Option<Slot>,
+  #[knus(child, unwrap(argument))]
+  error: Option<Slot>,
+  #[knus(child, unwrap(argument))]
+  warning: Option<Slot>,
+}
+
+impl From<RolesRepr> for ColorRoles {
+  fn from(val: RolesRepr) -> Self {
+    let default = ColorRoles::DEFAULT;
+
+    ColorRoles {
+      background: val.background.unwrap_or(default.background),
+      background_dark: val.background_dark.unwrap_or(default.background_dark),
+      surface: val.surface.unwrap_or(default.surface),
+      selection: val.selection.unwrap_or(default.selection),
+      border: val.border.unwrap_or(default.border),
+      foreground: val.foreground.unwrap_or(default.foreground),
+      foreground_muted: val.foreground_muted.unwrap_or(default.foreground_muted),
+      accent: val.accent.unwrap_or(default.accent),
+      accent_secondary: val.accent_secondary.unwrap_or(default.accent_secondary),
+      error: val.error.unwrap_or(default.error),
+      warning: val.warning.unwrap_or(default.warning),
+    }
+  }
+}
+
 #[derive(Debug, Clone, knus::Decode)]
 struct Base16Repr {
   #[knus(child, unwrap(argument))]
@@ -76,14 +131,34 @@ struct Base16Repr {
   pub base0e: HexColor,
   #[knus(child, unwrap(argument))]
   pub base0f: HexColor,
+
+  #[knus(child, unwrap(argument))]
+  pub base10: Option<HexColor>,
+  #[knus(child, unwrap(argument))]
+  pub base11: Option<HexColor>,
+  #[knus(child, unwrap(argument))]
+  pub base12: Option<HexColor>,
+  #[knus(child, unwrap(argument))]
+  pub base13: Option<HexColor>,
+  #[knus(child, unwrap(argument))]
+  pub base14: Option<HexColor>,
+  #[knus(child, unwrap(argument))]
+  pub base15: Option<HexColor>,
+  #[knus(child, unwrap(argument))]
+  pub base16: Option<HexColor>,
+  #[knus(child, unwrap(argument))]
+  pub base17: Option<HexColor>,
+
+  #[knus(child, default)]
+  pub roles: RolesRepr,
 }
 
 impl From<Base16Repr> for Base16Theme {
   fn from(val: Base16Repr) -> Self {
     Base16Theme {
       base00: val.base00.0,
-      base02: val.base01.0,
-      base01: val.base02.0,
+      base01: val.base01.0,
+      base02: val.base02.0,
       base03: val.base03.0,
       base04: val.base04.0,
       base05: val.base05.0,
@@ -97,6 +172,18 @@ impl From<Base16Repr> for Base16Theme {
       base0D: val.base0d.0,
       base0E: val.base0e.0,
       base0F: val.base0f.0,
+
+      base10: val.base10.map_or(val.base00.0, |c| c.0),
+      base11: val.base11.map_or(val.base00.0, |c| c.0),
+      base12: val.base12.map_or(val.base08.0, |c| c.0),
+      base13: val.base13.map_or(val.base0a.0, |c| c.0),
+      base14: val.base14.map_or(val.base0b.0, |c| c.0),
+      base15: val.base15.map_or(val.base0c.0, |c| c.0),
+      base16: val.base16.map_or(val.base0d.0, |c| c.0),
+      base17: val.base17.map_or(val.base0e.0, |c| c.0),
+
+      roles: val.roles.into(),
+      style: ThemeStyle::DEFAULT,
     }
   }
 }
@@ -109,6 +196,97 @@ where
     node: &knus::ast::SpannedNode<S>,
     ctx: &mut knus::decode::Context<S>,
   ) -> Result<Self, knus::errors::DecodeError<S>> {
+    // `theme "<name>"` selects a bundled scheme
+    if let Some(name_value) = node.arguments.first() {
+      let name: String = knus::traits::DecodeScalar::decode(name_value, ctx)?;
+
+      let theme = bundled_scheme(&name).ok_or_else(|| {
+        let names = bundled_scheme_names().collect::<Vec<_>>().join(", ");
+        knus::errors::DecodeError::conversion(
+          &name_value.literal,
+          format!("unknown scheme `{name}`, expected one of {names}"),
+        )
+      })?;
+
+      return Ok(Base16Theme {
+        roles: decode_roles(node, ctx)?,
+        ..theme
+      });
+    }
+
+    // `theme file="<path>"` loads a base16 YAML or JSON scheme
+    if let Some(file_value) = node
+      .properties
+      .iter()
+      .find_map(|(key, value)| (&***key == "file").then_some(value))
+    {
+      let path: String = knus::traits::DecodeScalar::decode(file_value, ctx)?;
+
+      let theme = load_scheme_file(&path)
+        .map_err(|error| knus::errors::DecodeError::conversion(&file_value.literal, error))?;
+
+      return Ok(Base16Theme {
+        roles: decode_roles(node, ctx)?,
+        ..theme
+      });
+    }
+
+    // Otherwise the colors are written as `baseXX` children
     Ok(Base16Repr::decode_node(node, ctx)?.into())
   }
 }
+
+/// Decode the `roles` child of a theme node that selects a bundled scheme or a scheme file
+fn decode_roles<S>(
+  node: &knus::ast::SpannedNode<S>,
+  ctx: &mut knus::decode::Context<S>,
+) -> Result<ColorRoles, knus::errors::DecodeError<S>>
+where
+  S: knus::traits::ErrorSpan,
+{
+  let mut roles = ColorRoles::DEFAULT;
+
+  for child in node.children.iter().flat_map(|children| children.iter()) {
+    if &**child.node_name == "roles" {
+      roles = <RolesRepr as knus::Decode<S>>::decode_node(child, ctx)?.into();
+    } else {
+      ctx.emit_error(knus::errors::DecodeError::unexpected(
+        &child.node_name,
+        "node",
+        format!(
+          "unexpected node `{}`, only `roles` can be set with a scheme name or file",
+          &*child.node_name
+        ),
+      ));
+    }
+  }
+
+  Ok(roles)
+}
+
+#[cfg(test)]
+mod tests {
+  use iced::Color;
+
+  use crate::{config::Config, theme::ColorScheme};
+
+  #[test]
+  fn colors_keep_their_slots() {
+    let config: Config = knus::parse(
+      "config.kdl",
+      r##"
+        theme {
+          base00 "#000000"; base01 "#010101"; base02 "#020202"; base03 "#030303"
+          base04 "#040404"; base05 "#050505"; base06 "#060606"; base07 "#070707"
+          base08 "#080808"; base09 "#090909"; base0a "#0a0a0a"; base0b "#0b0b0b"
+          base0c "#0c0c0c"; base0d "#0d0d0d"; base0e "#0e0e0e"; base0f "#0f0f0f"
+        }
+      "##,
+    )
+    .unwrap();
+    let theme = config.theme(ColorScheme::Dark);
+
+    assert_eq!(theme.base01, Color::from_rgb8(1, 1, 1));
+    assert_eq!(theme.base02, Color::from_rgb8(2, 2, 2));
+  }
+}
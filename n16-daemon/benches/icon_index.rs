@@ -0,0 +1,67 @@
+//! Icon index benchmarks on a synthetic icon theme with 50k icons
+
+use std::{
+  fs::{self, File},
+  hint::black_box,
+  path::{Path, PathBuf},
+};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use n16_daemon::IconIndex;
+
+const SIZES: [&str; 10] = [
+  "16x16", "22x22", "24x24", "32x32", "48x48", "64x64", "96x96", "128x128", "256x256", "scalable",
+];
+const ICONS_PER_DIR: usize = 5_000;
+
+/// Create `hicolor/<size>/apps/icon-<n>.<ext>` for every size, returning the size directories
+fn create_theme(root: &Path) -> Vec<PathBuf> {
+  SIZES
+    .iter()
+    .map(|size| {
+      let dir = root.join("hicolor").join(size).join("apps");
+      fs::create_dir_all(&dir).unwrap();
+
+      let extension = if *size == "scalable" { "svg" } else { "png" };
+      for n in 0..ICONS_PER_DIR {
+        File::create(dir.join(format!("icon-{n}.{extension}"))).unwrap();
+      }
+
+      dir
+    })
+    .collect()
+}
+
+fn icon_index(c: &mut Criterion) {
+  let theme = tempfile::tempdir().unwrap();
+  let cache = tempfile::tempdir().unwrap();
+  let cache_path = cache.path().join("icon-index.json");
+  let dirs = create_theme(theme.path());
+
+  let mut group = c.benchmark_group("icon_index");
+  group.sample_size(10);
+
+  group.bench_function("build_uncached", |b| {
+    b.iter(|| IconIndex::build_with_cache(dirs.iter().map(PathBuf::as_path), None));
+  });
+
+  // Every directory is up to date in the cache, so nothing is scanned or written
+  IconIndex::build_with_cache(dirs.iter().map(PathBuf::as_path), Some(&cache_path));
+  group.bench_function("build_cached", |b| {
+    b.iter(|| IconIndex::build_with_cache(dirs.iter().map(PathBuf::as_path), Some(&cache_path)));
+  });
+
+  let index = IconIndex::build_with_cache(dirs.iter().map(PathBuf::as_path), None);
+  group.bench_function("find_in_every_size", |b| {
+    b.iter(|| {
+      for dir in &dirs {
+        black_box(index.find(black_box("icon-2500"), dir));
+      }
+    });
+  });
+
+  group.finish();
+}
+
+criterion_group!(benches, icon_index);
+criterion_main!(benches);
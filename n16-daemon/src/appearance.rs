@@ -0,0 +1,199 @@
+use std::sync::{LazyLock, RwLock};
+
+use futures_lite::{Stream, StreamExt};
+use n16_core::{
+  config::{Config, ThemeConfig},
+  theme::{ColorScheme, ThemeStyle},
+};
+use n16_ipc::theme::{Palette, ThemeMode};
+use tokio::sync::watch;
+use tokio_stream::wrappers::WatchStream;
+use zbus::{Connection, MatchRule, MessageStream, message, zvariant::OwnedValue};
+
+const DESTINATION: &str = "org.freedesktop.portal.Desktop";
+const PATH: &str = "/org/freedesktop/portal/desktop";
+const INTERFACE: &str = "org.freedesktop.portal.Settings";
+const NAMESPACE: &str = "org.freedesktop.appearance";
+const KEY: &str = "color-scheme";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct State {
+  /// Preference reported by xdg-desktop-portal
+  system: ColorScheme,
+  /// Override set with [`n16_ipc::theme::Request::SetMode`]
+  mode: ThemeMode,
+}
+
+impl State {
+  fn color_scheme(self) -> ColorScheme {
+    match self.mode {
+      ThemeMode::Light => ColorScheme::Light,
+      ThemeMode::Dark => ColorScheme::Dark,
+      ThemeMode::Auto => self.system,
+    }
+  }
+}
+
+static STATE: LazyLock<watch::Sender<State>> = LazyLock::new(|| watch::channel(State::default()).0);
+
+/// Themes and widget style of the loaded config, see [`set_themes`]
+static THEMES: RwLock<Option<(ThemeConfig, ThemeStyle)>> = RwLock::new(None);
+
+/// Color scheme the themes are currently chosen for
+pub fn color_scheme() -> ColorScheme {
+  STATE.borrow().color_scheme()
+}
+
+/// The current color scheme, followed by every change to it
+pub fn color_scheme_changes() -> impl Stream<Item = ColorScheme> {
+  WatchStream::new(STATE.subscribe()).map(State::color_scheme)
+}
+
+/// Colors of the theme for the current color scheme
+pub fn palette() -> Palette {
+  let themes = THEMES.read().unwrap();
+
+  match &*themes {
+    Some((themes, style)) => themes.theme(color_scheme(), *style).palette(),
+    None => Config::default().theme(color_scheme()).palette(),
+  }
+}
+
+/// Serve the themes of `config` from [`palette`], called whenever the config is loaded
+pub fn set_themes(config: &Config) {
+  *THEMES.write().unwrap() = Some((config.themes().clone(), config.style().theme_style()));
+}
+
+pub fn set_mode(mode: ThemeMode) {
+  update(|state| state.mode = mode);
+}
+
+fn update(modify: impl FnOnce(&mut State)) {
+  STATE.send_if_modified(|state| {
+    let previous = state.color_scheme();
+    modify(state);
+    state.color_scheme() != previous
+  });
+}
+
+/// Follow the `color-scheme` setting of xdg-desktop-portal.
+/// Without a portal the themes are chosen as if there was no preference.
+pub async fn watch_portal() {
+  if let Err(error) = follow_portal().await {
+    eprintln!("Failed to follow the color scheme from xdg-desktop-portal: {error}");
+  }
+}
+
+async fn follow_portal() -> zbus::Result<()> {
+  let connection = Connection::session().await?;
+
+  let rule = MatchRule::builder()
+    .msg_type(message::Type::Signal)
+    .path(PATH)?
+    .interface(INTERFACE)?
+    .member("SettingChanged")?
+    .arg(0, NAMESPACE)?
+    .arg(1, KEY)?
+    .build();
+
+  // Subscribe before reading the setting so no change in between is missed
+  let mut changes = MessageStream::for_match_rule(rule, &connection, None).await?;
+
+  let system = read_color_scheme(&connection).await?;
+  update(|state| state.system = system);
+
+  while let Some(message) = changes.next().await {
+    let (_, _, value): (String, String, OwnedValue) = message?.body().deserialize()?;
+    let system = parse_color_scheme(value)?;
+    update(|state| state.system = system);
+  }
+
+  Ok(())
+}
+
+/// Read the `color-scheme` setting
+pub async fn read_color_scheme(connection: &Connection) -> zbus::Result<ColorScheme> {
+  let reply = connection
+    .call_method(
+      Some(DESTINATION),
+      PATH,
+      Some(INTERFACE),
+      "ReadOne",
+      &(NAMESPACE, KEY),
+    )
+    .await?;
+  let value: OwnedValue = reply.body().deserialize()?;
+
+  parse_color_scheme(value)
+}
+
+fn parse_color_scheme(value: OwnedValue) -> zbus::Result<ColorScheme> {
+  Ok(match u32::try_from(value)? {
+    1 => ColorScheme::Dark,
+    2 => ColorScheme::Light,
+    _ => ColorScheme::NoPreference,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::{
+    Arc,
+    atomic::{AtomicU32, Ordering},
+  };
+
+  use super::*;
+  use crate::test_bus::TestBus;
+
+  #[derive(Default)]
+  struct MockPortal {
+    color_scheme: Arc<AtomicU32>,
+  }
+
+  #[zbus::interface(name = "org.freedesktop.portal.Settings")]
+  impl MockPortal {
+    fn read_one(&self, namespace: &str, key: &str) -> zbus::fdo::Result<OwnedValue> {
+      if (namespace, key) == (NAMESPACE, KEY) {
+        Ok(OwnedValue::from(self.color_scheme.load(Ordering::Relaxed)))
+      } else {
+        Err(zbus::fdo::Error::Failed(format!(
+          "Unknown setting {namespace}.{key}"
+        )))
+      }
+    }
+  }
+
+  #[tokio::test]
+  async fn read_color_scheme_from_portal() {
+    let bus = TestBus::start();
+    let portal = MockPortal::default();
+    let color_scheme = Arc::clone(&portal.color_scheme);
+    let _portal = bus.serve(DESTINATION, PATH, portal).await;
+    let connection = bus.connect().await;
+
+    for (value, expected) in [
+      (0, ColorScheme::NoPreference),
+      (1, ColorScheme::Dark),
+      (2, ColorScheme::Light),
+      (3, ColorScheme::NoPreference),
+    ] {
+      color_scheme.store(value, Ordering::Relaxed);
+      assert_eq!(read_color_scheme(&connection).await.unwrap(), expected);
+    }
+  }
+
+  #[test]
+  fn mode_overrides_system_preference() {
+    let state = |system, mode| State { system, mode }.color_scheme();
+
+    assert_eq!(state(ColorScheme::Dark, ThemeMode::Auto), ColorScheme::Dark);
+    assert_eq!(
+      state(ColorScheme::Dark, ThemeMode::Light),
+      ColorScheme::Light
+    );
+    assert_eq!(
+      state(ColorScheme::NoPreference, ThemeMode::Dark),
+      ColorScheme::Dark
+    );
+  }
+}
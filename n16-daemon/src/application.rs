@@ -1,4 +1,14 @@
-use std::{fmt::Debug, marker::PhantomData, ops::ControlFlow, thread};
+use std::{
+  collections::HashSet,
+  fmt::Debug,
+  marker::PhantomData,
+  ops::ControlFlow,
+  sync::{LazyLock, Mutex},
+  thread,
+};
+
+use iced::Font;
+use n16_core::config::StyleConfig;
 
 #[derive(Debug, Clone)]
 pub struct ApplicationRequest<R> {
@@ -36,6 +46,29 @@ impl<R: Debug> ApplicationRequest<R> {
   }
 }
 
+/// Font family names used so far. iced only accepts static font names, so each name is leaked
+/// once and reused afterwards.
+static FONT_FAMILIES: LazyLock<Mutex<HashSet<&'static str>>> = LazyLock::new(Mutex::default);
+
+fn intern_font_family(family: &str) -> &'static str {
+  let mut families = FONT_FAMILIES.lock().unwrap();
+
+  if let Some(interned) = families.get(family) {
+    return interned;
+  }
+
+  let interned: &'static str = Box::leak(family.into());
+  families.insert(interned);
+  interned
+}
+
+/// Default font of an application, using the configured font family if there is one
+pub fn default_font(style: &StyleConfig) -> Font {
+  style.font().map_or(Font::DEFAULT, |family| {
+    Font::with_name(intern_font_family(family))
+  })
+}
+
 pub type RequestChannel<R> = async_channel::Receiver<ApplicationRequest<R>>;
 
 pub trait N16Application {
@@ -98,3 +131,17 @@ where
 {
   Box::new(WrappedApplication::<A>::run())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn font_family_is_interned_once() {
+    let first = intern_font_family("Inter");
+    let second = intern_font_family(&String::from("Inter"));
+
+    assert!(std::ptr::eq(first, second));
+    assert_eq!(intern_font_family("Noto Sans"), "Noto Sans");
+  }
+}
@@ -1,34 +1,110 @@
+use std::collections::HashMap;
+
 use iced::{
-  Length, Subscription, Task, time,
-  widget::{Space, row},
+  Element, Length, Subscription, Task,
+  alignment::Horizontal,
+  mouse,
+  widget::{Row, container, mouse_area, row},
   window,
 };
 use iced_layershell::{
-  reexport::{Anchor, KeyboardInteractivity, NewLayerShellSettings},
-  settings::{LayerShellSettings, StartMode},
+  reexport::{Anchor, KeyboardInteractivity, NewLayerShellSettings, OutputOption},
+  settings::{LayerShellSettings, Settings, StartMode},
   to_layer_message,
 };
-use n16_core::theme::{self};
+use n16_core::{
+  config::{
+    self, BarConfig, BarEdge, BarLayout, BarModuleConfig, Config, StyleConfig, ThemeConfig,
+  },
+  theme::{self, Base16Theme, ColorScheme},
+};
 use n16_ipc::{Response, bar::Request};
+use wayland_client::{Connection, protocol::wl_output::WlOutput};
 
 use crate::{
-  application::{ApplicationRequest, N16Application, RequestChannel},
-  bar::components::clock,
+  appearance,
+  application::{ApplicationRequest, N16Application, RequestChannel, default_font},
+  bar::{
+    modules::{AnyModule, ModuleMessage},
+    outputs::{OutputEvent, watch},
+  },
 };
 
-mod components;
+mod modules;
+mod outputs;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Zone {
+  Left,
+  Center,
+  Right,
+}
+
+/// Identifies a module instance in one of the open bars
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ModuleKey {
+  output: Option<String>,
+  zone: Zone,
+  index: usize,
+}
+
+/// An open bar with the modules shown in it
+struct BarWindow {
+  id: window::Id,
+  left: Vec<Box<dyn AnyModule>>,
+  center: Vec<Box<dyn AnyModule>>,
+  right: Vec<Box<dyn AnyModule>>,
+}
+
+impl BarWindow {
+  fn new(id: window::Id, layout: &BarLayout) -> Self {
+    let create = |modules: &[BarModuleConfig]| modules.iter().filter_map(modules::create).collect();
+
+    Self {
+      id,
+      left: create(layout.left()),
+      center: create(layout.center()),
+      right: create(layout.right()),
+    }
+  }
+
+  fn zone(&self, zone: Zone) -> &[Box<dyn AnyModule>] {
+    match zone {
+      Zone::Left => &self.left,
+      Zone::Center => &self.center,
+      Zone::Right => &self.right,
+    }
+  }
+
+  fn zone_mut(&mut self, zone: Zone) -> &mut [Box<dyn AnyModule>] {
+    match zone {
+      Zone::Left => &mut self.left,
+      Zone::Center => &mut self.center,
+      Zone::Right => &mut self.right,
+    }
+  }
+}
 
 #[to_layer_message(multi)]
 #[derive(Debug, Clone)]
 pub enum Message {
-  Tick(chrono::DateTime<chrono::Local>),
   ShowBar(bool),
+  ColorSchemeChanged(ColorScheme),
+  Output(OutputEvent),
+  Module(ModuleKey, ModuleMessage),
   RequestRecieved(ApplicationRequest<Request>),
 }
 
 pub struct Bar {
-  now: chrono::DateTime<chrono::Local>,
-  window_id: Option<window::Id>,
+  themes: ThemeConfig,
+  color_scheme: ColorScheme,
+  style: StyleConfig,
+  config: BarConfig,
+  visible: bool,
+  /// Names of the connected outputs with their `wl_output`, or `None` if outputs can't be tracked
+  outputs: Option<Vec<(String, WlOutput)>>,
+  /// Open bars by their output, `None` for a bar on the output chosen by the compositor
+  windows: HashMap<Option<String>, BarWindow>,
 }
 
 impl Bar {
@@ -49,54 +125,143 @@ impl Bar {
     }
   }
 
-  fn show_bar(&mut self) -> Task<Message> {
-    if self.window_id.is_some() {
-      return Task::none();
+  fn handle_output_event(&mut self, event: OutputEvent) -> Task<Message> {
+    match event {
+      OutputEvent::Added(name, proxy) => {
+        if let Some(outputs) = &mut self.outputs {
+          outputs.retain(|(output, _)| *output != name);
+          outputs.push((name, proxy));
+        }
+
+        self.update_windows()
+      }
+
+      OutputEvent::Removed(name) => {
+        if let Some(outputs) = &mut self.outputs {
+          outputs.retain(|(output, _)| *output != name);
+        }
+
+        self.update_windows()
+      }
+
+      OutputEvent::Unavailable => {
+        self.outputs = None;
+        self.update_windows()
+      }
+    }
+  }
+
+  /// Outputs that should have a bar
+  fn shown_outputs(&self) -> Vec<Option<String>> {
+    if !self.visible {
+      return Vec::new();
     }
 
-    let (id, task) = Message::layershell_open(NewLayerShellSettings {
-      size: Some((0, 30)),
-      anchor: Anchor::Bottom | Anchor::Left | Anchor::Right,
-      keyboard_interactivity: KeyboardInteractivity::None,
-      exclusive_zone: Some(30),
-      ..Default::default()
+    match &self.outputs {
+      Some(outputs) => outputs
+        .iter()
+        .filter(|(output, _)| self.config.shows_output(output))
+        .map(|(output, _)| Some(output.clone()))
+        .collect(),
+      None => vec![None],
+    }
+  }
+
+  /// Open and close bars so every shown output has one
+  fn update_windows(&mut self) -> Task<Message> {
+    let shown = self.shown_outputs();
+    let mut tasks = Vec::new();
+
+    self.windows.retain(|output, window| {
+      let keep = shown.contains(output);
+      if !keep {
+        tasks.push(window::close(window.id));
+      }
+      keep
     });
 
-    self.window_id = Some(id);
-    task
+    for output in shown {
+      if !self.windows.contains_key(&output) {
+        let (id, task) = self.open_bar(output.as_deref());
+        let window = BarWindow::new(id, self.config.layout(output.as_deref()));
+        self.windows.insert(output, window);
+        tasks.push(task);
+      }
+    }
+
+    Task::batch(tasks)
   }
 
-  fn hide_bar(&mut self) -> Task<Message> {
-    let Some(window_id) = self.window_id else {
-      return Task::none();
+  fn open_bar(&self, output: Option<&str>) -> (window::Id, Task<Message>) {
+    let bar_style = self.style.bar();
+    let output = output.and_then(|name| {
+      self
+        .outputs
+        .iter()
+        .flatten()
+        .find(|(output, _)| output == name)
+        .map(|(_, proxy)| proxy.clone())
+    });
+
+    let edge = match bar_style.edge() {
+      BarEdge::Top => Anchor::Top,
+      BarEdge::Bottom => Anchor::Bottom,
     };
 
-    self.window_id = None;
-    window::close(window_id)
+    Message::layershell_open(NewLayerShellSettings {
+      size: Some((0, bar_style.height())),
+      anchor: edge | Anchor::Left | Anchor::Right,
+      keyboard_interactivity: KeyboardInteractivity::None,
+      exclusive_zone: Some(bar_style.height().try_into().unwrap_or(i32::MAX)),
+      output_option: output.map_or(OutputOption::None, OutputOption::Output),
+      ..Default::default()
+    })
   }
 }
 
 impl Bar {
   pub fn new() -> Self {
+    Self::with_config(&config::load_config_file().unwrap_or_default())
+  }
+
+  fn with_config(config: &Config) -> Self {
     Self {
-      now: chrono::offset::Local::now(),
-      window_id: None,
+      themes: config.themes().clone(),
+      color_scheme: appearance::color_scheme(),
+      style: config.style().clone(),
+      config: config.bar().clone(),
+      visible: false,
+      outputs: Some(Vec::new()),
+      windows: HashMap::new(),
     }
   }
 
   pub fn update(&mut self, message: Message) -> Task<Message> {
     match message {
-      Message::Tick(time) => {
-        self.now = time;
+      Message::ShowBar(show) => {
+        self.visible = show;
+        self.update_windows()
+      }
+
+      Message::ColorSchemeChanged(scheme) => {
+        self.color_scheme = scheme;
         Task::none()
       }
 
-      Message::ShowBar(show) => {
-        if show {
-          self.show_bar()
-        } else {
-          self.hide_bar()
-        }
+      Message::Output(event) => self.handle_output_event(event),
+
+      Message::Module(key, message) => {
+        let Some(module) = self
+          .windows
+          .get_mut(&key.output)
+          .and_then(|window| window.zone_mut(key.zone).get_mut(key.index))
+        else {
+          return Task::none();
+        };
+
+        module
+          .update(message)
+          .map(move |m| Message::Module(key.clone(), m))
       }
 
       Message::RequestRecieved(request) => self.handle_request(request),
@@ -105,22 +270,76 @@ impl Bar {
     }
   }
 
-  pub fn view(&self, _id: window::Id) -> iced::Element<'_, Message, theme::Base16Theme> {
+  pub fn view(&self, id: window::Id) -> Element<'_, Message, theme::Base16Theme> {
+    let Some((output, window)) = self.windows.iter().find(|(_, window)| window.id == id) else {
+      return "".into();
+    };
+
+    let zone = |zone| {
+      let modules = window.zone(zone).iter().enumerate().map(|(index, module)| {
+        let key = ModuleKey {
+          output: output.clone(),
+          zone,
+          index,
+        };
+
+        Element::from(
+          mouse_area(module.view())
+            .on_press(ModuleMessage::Clicked(mouse::Button::Left))
+            .on_right_press(ModuleMessage::Clicked(mouse::Button::Right))
+            .on_middle_press(ModuleMessage::Clicked(mouse::Button::Middle))
+            .on_scroll(ModuleMessage::Scrolled),
+        )
+        .map(move |m| Message::Module(key.clone(), m))
+      });
+
+      Row::with_children(modules).height(Length::Fill)
+    };
+
     row![
-      Space::new().width(Length::Fill),
-      clock::view(&self.now).into()
+      container(zone(Zone::Left)).width(Length::Fill),
+      zone(Zone::Center),
+      container(zone(Zone::Right))
+        .width(Length::Fill)
+        .align_x(Horizontal::Right),
     ]
-    .padding(5)
+    .padding(self.style.bar().padding())
     .into()
   }
 
-  #[expect(
-    clippy::unused_self,
-    reason = "Method is expected to use self in the future"
-  )]
+  pub fn theme(&self, _id: window::Id) -> Base16Theme {
+    self
+      .themes
+      .theme(self.color_scheme, self.style.theme_style())
+  }
+
   pub fn subscription(&self) -> Subscription<Message> {
-    time::every(time::Duration::from_millis(500))
-      .map(|_| Message::Tick(chrono::offset::Local::now()))
+    let modules = self.windows.iter().flat_map(|(output, window)| {
+      [Zone::Left, Zone::Center, Zone::Right]
+        .into_iter()
+        .flat_map(move |zone| {
+          window
+            .zone(zone)
+            .iter()
+            .enumerate()
+            .map(move |(index, module)| {
+              let key = ModuleKey {
+                output: output.clone(),
+                zone,
+                index,
+              };
+
+              module
+                .subscription()
+                .with(key)
+                .map(|(key, m)| Message::Module(key, m))
+            })
+        })
+    });
+
+    Subscription::batch(modules.chain([
+      Subscription::run(appearance::color_scheme_changes).map(Message::ColorSchemeChanged),
+    ]))
   }
 }
 
@@ -128,18 +347,45 @@ impl N16Application for Bar {
   type Request = Request;
 
   fn run(request_rx: RequestChannel<Self::Request>) {
+    let style = config::load_config_file()
+      .unwrap_or_default()
+      .style()
+      .clone();
+
+    // Shared with `iced_layershell`, so bars can be opened on the tracked outputs
+    let connection = match Connection::connect_to_env() {
+      Ok(connection) => connection,
+      Err(error) => {
+        eprintln!("Failed to connect to the Wayland compositor: {error}");
+        return;
+      }
+    };
+
     let _ = iced_layershell::daemon(
-      move || {
-        (
-          Bar::new(),
-          Task::stream(request_rx.clone()).map(Message::RequestRecieved),
-        )
+      {
+        let connection = connection.clone();
+        move || {
+          (
+            Bar::new(),
+            Task::batch([
+              Task::stream(request_rx.clone()).map(Message::RequestRecieved),
+              Task::stream(watch(connection.clone())).map(Message::Output),
+            ]),
+          )
+        }
       },
       "n16_bar",
       Bar::update,
       Bar::view,
     )
+    .settings(Settings {
+      with_connection: Some(connection.into()),
+      ..Default::default()
+    })
     .subscription(Bar::subscription)
+    .theme(Bar::theme)
+    .default_font(default_font(&style))
+    .default_text_size(style.font_size())
     .layer_settings(LayerShellSettings {
       start_mode: StartMode::Background,
       ..Default::default()
@@ -147,3 +393,42 @@ impl N16Application for Bar {
     .run();
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use std::os::unix::net::UnixStream;
+
+  use wayland_client::{Proxy, backend::Backend};
+
+  use super::*;
+
+  fn shown(bar: &Bar) -> Vec<Option<&str>> {
+    let mut outputs: Vec<_> = bar.windows.keys().map(Option::as_deref).collect();
+    outputs.sort_unstable();
+    outputs
+  }
+
+  #[test]
+  fn bars_follow_connected_outputs() {
+    // Outputs are only passed on to `iced_layershell`, so they don't need a compositor
+    let (stream, _) = UnixStream::pair().unwrap();
+    let backend = Backend::connect(stream).unwrap();
+    let output = || WlOutput::inert(backend.downgrade());
+
+    let mut bar = Bar::with_config(&Config::default());
+    let _ = bar.update(Message::Output(OutputEvent::Added("DP-1".into(), output())));
+    assert!(shown(&bar).is_empty());
+
+    let _ = bar.update(Message::ShowBar(true));
+    assert_eq!(shown(&bar), [Some("DP-1")]);
+
+    let _ = bar.update(Message::Output(OutputEvent::Added("DP-2".into(), output())));
+    assert_eq!(shown(&bar), [Some("DP-1"), Some("DP-2")]);
+
+    let _ = bar.update(Message::Output(OutputEvent::Removed("DP-1".into())));
+    assert_eq!(shown(&bar), [Some("DP-2")]);
+
+    let _ = bar.update(Message::Output(OutputEvent::Unavailable));
+    assert_eq!(shown(&bar), [None]);
+  }
+}
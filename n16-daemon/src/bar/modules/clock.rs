@@ -0,0 +1,61 @@
+use std::fmt::Write;
+
+use iced::{
+  Element, Length, Subscription, Task, time,
+  widget::{container, text},
+};
+use n16_core::{config::ClockConfig, theme::Base16Theme};
+
+use super::BarModule;
+
+/// Format used when the configured one is invalid
+const FALLBACK_FORMAT: &str = "%H:%M";
+
+#[derive(Debug, Clone)]
+pub struct Tick(chrono::DateTime<chrono::Local>);
+
+pub struct ClockModule {
+  format: String,
+  now: chrono::DateTime<chrono::Local>,
+}
+
+impl ClockModule {
+  pub fn new(config: &ClockConfig) -> Self {
+    let now = chrono::offset::Local::now();
+
+    // chrono only reports invalid formats when they are written
+    let format = if write!(String::new(), "{}", now.format(config.format())).is_ok() {
+      config.format().to_string()
+    } else {
+      eprintln!(
+        "Invalid clock format `{}`, using `{FALLBACK_FORMAT}`",
+        config.format()
+      );
+      FALLBACK_FORMAT.to_string()
+    };
+
+    Self { format, now }
+  }
+}
+
+impl BarModule for ClockModule {
+  type Message = Tick;
+
+  fn view(&self) -> Element<'_, Tick, Base16Theme> {
+    let time = self.now.format(&self.format).to_string();
+
+    container(text(time))
+      .center_y(Length::Fill)
+      .padding([0, 8])
+      .into()
+  }
+
+  fn update(&mut self, Tick(now): Tick) -> Task<Tick> {
+    self.now = now;
+    Task::none()
+  }
+
+  fn subscription(&self) -> Subscription<Tick> {
+    time::every(time::Duration::from_millis(500)).map(|_| Tick(chrono::offset::Local::now()))
+  }
+}
@@ -0,0 +1,110 @@
+use std::{any::Any, fmt, sync::Arc};
+
+use iced::{
+  Element, Subscription, Task,
+  mouse::{self, ScrollDelta},
+};
+use n16_core::{config::BarModuleConfig, theme::Base16Theme};
+
+use clock::ClockModule;
+
+mod clock;
+
+/// A part of the bar, created from its config for every bar it is shown in
+pub trait BarModule: Send + 'static {
+  /// Messages of the module's own subscription and tasks
+  type Message: fmt::Debug + Clone + Send + Sync + 'static;
+
+  fn view(&self) -> Element<'_, Self::Message, Base16Theme>;
+
+  fn update(&mut self, _message: Self::Message) -> Task<Self::Message> {
+    Task::none()
+  }
+
+  fn subscription(&self) -> Subscription<Self::Message> {
+    Subscription::none()
+  }
+
+  fn on_click(&mut self, _button: mouse::Button) -> Task<Self::Message> {
+    Task::none()
+  }
+
+  fn on_scroll(&mut self, _delta: ScrollDelta) -> Task<Self::Message> {
+    Task::none()
+  }
+}
+
+/// Message of any bar module, routed to the module by the bar
+#[derive(Debug, Clone)]
+pub enum ModuleMessage {
+  /// The module was clicked, see [`BarModule::on_click`]
+  Clicked(mouse::Button),
+  /// The pointer scrolled over the module, see [`BarModule::on_scroll`]
+  Scrolled(ScrollDelta),
+  /// A [`BarModule::Message`] of the module
+  Module(ModulePayload),
+}
+
+impl ModuleMessage {
+  fn wrap<M: Send + Sync + 'static>(message: M) -> Self {
+    Self::Module(ModulePayload(Arc::new(message)))
+  }
+}
+
+/// A [`BarModule::Message`] with its type erased
+#[derive(Clone)]
+pub struct ModulePayload(Arc<dyn Any + Send + Sync>);
+
+impl fmt::Debug for ModulePayload {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("ModulePayload")
+  }
+}
+
+/// A [`BarModule`] with its message type erased, so different modules can be stored together
+pub trait AnyModule: Send {
+  fn view(&self) -> Element<'_, ModuleMessage, Base16Theme>;
+
+  fn update(&mut self, message: ModuleMessage) -> Task<ModuleMessage>;
+
+  fn subscription(&self) -> Subscription<ModuleMessage>;
+}
+
+impl<T: BarModule> AnyModule for T {
+  fn view(&self) -> Element<'_, ModuleMessage, Base16Theme> {
+    BarModule::view(self).map(ModuleMessage::wrap)
+  }
+
+  fn update(&mut self, message: ModuleMessage) -> Task<ModuleMessage> {
+    let task = match message {
+      ModuleMessage::Clicked(button) => self.on_click(button),
+      ModuleMessage::Scrolled(delta) => self.on_scroll(delta),
+      ModuleMessage::Module(ModulePayload(payload)) => {
+        // Payloads only reach the module that wrapped them, so this only fails for messages
+        // of a module that was replaced in the meantime
+        let Ok(message) = payload.downcast::<T::Message>() else {
+          return Task::none();
+        };
+
+        BarModule::update(self, Arc::unwrap_or_clone(message))
+      }
+    };
+
+    task.map(ModuleMessage::wrap)
+  }
+
+  fn subscription(&self) -> Subscription<ModuleMessage> {
+    BarModule::subscription(self).map(ModuleMessage::wrap)
+  }
+}
+
+/// Create the module for `config`, or `None` for a module that doesn't exist
+pub fn create(config: &BarModuleConfig) -> Option<Box<dyn AnyModule>> {
+  match config {
+    BarModuleConfig::Clock(config) => Some(Box::new(ClockModule::new(config))),
+    BarModuleConfig::Unknown(name) => {
+      eprintln!("Unknown bar module `{name}`, skipping it");
+      None
+    }
+  }
+}
@@ -0,0 +1,172 @@
+use std::{collections::HashMap, error::Error, thread};
+
+use futures_lite::Stream;
+use wayland_client::{
+  Connection, Dispatch, Proxy, QueueHandle,
+  globals::{GlobalListContents, registry_queue_init},
+  protocol::{
+    wl_output::{self, WlOutput},
+    wl_registry::{self, WlRegistry},
+  },
+};
+
+/// Output names are only sent since version 4 of `wl_output`
+const OUTPUT_VERSION: u32 = 4;
+
+/// Change to the connected outputs
+#[derive(Debug, Clone)]
+pub enum OutputEvent {
+  /// An output with its name, bound on the connection shared with `iced_layershell`
+  Added(String, WlOutput),
+  Removed(String),
+  /// Outputs can't be tracked, so the bar should be shown on the output chosen by the compositor
+  Unavailable,
+}
+
+struct Output {
+  proxy: WlOutput,
+  name: Option<String>,
+  /// Whether [`OutputEvent::Added`] was sent for this output
+  announced: bool,
+}
+
+struct State {
+  /// Outputs by their registry name
+  outputs: HashMap<u32, Output>,
+  events: async_channel::Sender<OutputEvent>,
+}
+
+impl State {
+  fn bind(&mut self, registry: &WlRegistry, qh: &QueueHandle<Self>, global: u32, version: u32) {
+    if version < OUTPUT_VERSION {
+      self.send(OutputEvent::Unavailable);
+    }
+
+    let proxy = registry.bind::<WlOutput, _, _>(global, version.min(OUTPUT_VERSION), qh, global);
+
+    self.outputs.insert(
+      global,
+      Output {
+        proxy,
+        name: None,
+        announced: false,
+      },
+    );
+  }
+
+  fn send(&self, event: OutputEvent) {
+    // Fails once the bar stopped listening, the thread then stops on the next dispatch error
+    let _ = self.events.try_send(event);
+  }
+}
+
+impl Dispatch<WlRegistry, GlobalListContents> for State {
+  fn event(
+    state: &mut Self,
+    registry: &WlRegistry,
+    event: wl_registry::Event,
+    _: &GlobalListContents,
+    _: &Connection,
+    qh: &QueueHandle<Self>,
+  ) {
+    match event {
+      wl_registry::Event::Global {
+        name,
+        interface,
+        version,
+      } if interface == WlOutput::interface().name => state.bind(registry, qh, name, version),
+
+      wl_registry::Event::GlobalRemove { name } => {
+        let Some(output) = state.outputs.remove(&name) else {
+          return;
+        };
+
+        if output.proxy.version() >= 3 {
+          output.proxy.release();
+        }
+
+        if let Some(name) = output.name.filter(|_| output.announced) {
+          state.send(OutputEvent::Removed(name));
+        }
+      }
+
+      _ => {}
+    }
+  }
+}
+
+impl Dispatch<WlOutput, u32> for State {
+  fn event(
+    state: &mut Self,
+    _: &WlOutput,
+    event: wl_output::Event,
+    global: &u32,
+    _: &Connection,
+    _: &QueueHandle<Self>,
+  ) {
+    let Some(output) = state.outputs.get_mut(global) else {
+      return;
+    };
+
+    match event {
+      wl_output::Event::Name { name } => output.name = Some(name),
+
+      // Sent once all properties of the output were sent
+      wl_output::Event::Done if !output.announced => {
+        if let Some(name) = output.name.clone() {
+          output.announced = true;
+          let proxy = output.proxy.clone();
+          state.send(OutputEvent::Added(name, proxy));
+        }
+      }
+
+      _ => {}
+    }
+  }
+}
+
+fn track_outputs(
+  connection: &Connection,
+  events: async_channel::Sender<OutputEvent>,
+) -> Result<(), Box<dyn Error>> {
+  let (globals, mut queue) = registry_queue_init::<State>(connection)?;
+  let qh = queue.handle();
+
+  let mut state = State {
+    outputs: HashMap::new(),
+    events,
+  };
+
+  globals.contents().with_list(|list| {
+    for global in list {
+      if global.interface == WlOutput::interface().name {
+        state.bind(globals.registry(), &qh, global.name, global.version);
+      }
+    }
+  });
+
+  while !state.events.is_closed() {
+    queue.blocking_dispatch(&mut state)?;
+  }
+
+  Ok(())
+}
+
+/// Connected outputs, followed by outputs being connected and disconnected.
+///
+/// `iced_layershell` doesn't report outputs, so they are tracked on its `connection` with a separate
+/// event queue. Bars can be opened on the reported outputs as soon as they are added.
+/// Compositors without version 4 of `wl_output` don't name outputs, so they are reported as
+/// [`OutputEvent::Unavailable`].
+pub fn watch(connection: Connection) -> impl Stream<Item = OutputEvent> {
+  let (events, receiver) = async_channel::unbounded();
+
+  thread::spawn(move || {
+    if let Err(error) = track_outputs(&connection, events.clone()) {
+      eprintln!("Failed to track outputs, showing a single bar: {error}");
+      let _ = events.try_send(OutputEvent::Unavailable);
+    }
+  });
+
+  receiver
+}
@@ -0,0 +1,20 @@
+use std::sync::LazyLock;
+
+use n16_ipc::Event;
+use tokio::sync::broadcast;
+
+/// Events buffered for each client before the oldest ones are dropped
+const CAPACITY: usize = 64;
+
+static EVENTS: LazyLock<broadcast::Sender<Event>> =
+  LazyLock::new(|| broadcast::channel(CAPACITY).0);
+
+/// Send an event to every client of the event stream
+pub fn send(event: Event) {
+  // Sending only fails when nobody is listening
+  let _ = EVENTS.send(event);
+}
+
+pub fn subscribe() -> broadcast::Receiver<Event> {
+  EVENTS.subscribe()
+}
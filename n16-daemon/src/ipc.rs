@@ -6,10 +6,11 @@ use tokio::net::{UnixListener, UnixStream};
 
 use futures_lite::{Stream, StreamExt};
 
-use n16_ipc::{Reply, Request};
+use n16_ipc::{Reply, Request, Response};
+use tokio::sync::broadcast::error::RecvError;
 use tokio_stream::wrappers::UnixListenerStream;
 
-use crate::application::ApplicationRequest;
+use crate::{application::ApplicationRequest, events};
 
 type RequestSender = async_channel::Sender<ApplicationRequest<Request>>;
 
@@ -55,6 +56,10 @@ async fn handle_stream(mut stream: UnixStream, output: &mut RequestSender) -> io
 
   let request = serde_json::from_str(&buf).map_err(|err| err.to_string());
 
+  if matches!(request, Ok(Request::EventStream)) {
+    return stream_events(write).await;
+  }
+
   let reply = match request {
     Ok(request) => process_request(request, output)
       .await
@@ -76,3 +81,28 @@ async fn process_request(request: Request, output: &mut RequestSender) -> Option
   let reply = reply_rx.recv().await.ok()?;
   Some(reply)
 }
+
+/// Reply to [`Request::EventStream`], then write every event as a json line until the client
+/// disconnects
+async fn stream_events(mut write: impl AsyncWriteExt + Unpin) -> io::Result<()> {
+  let mut events = events::subscribe();
+
+  let mut buf = serde_json::to_vec(&Reply::Ok(Response::Handled))?;
+  buf.push(b'\n');
+  write.write_all(&buf).await?;
+
+  loop {
+    let event = match events.recv().await {
+      Ok(event) => event,
+      Err(RecvError::Lagged(skipped)) => {
+        eprintln!("Event stream client lagged behind, skipped {skipped} events");
+        continue;
+      }
+      Err(RecvError::Closed) => return Ok(()),
+    };
+
+    let mut buf = event.to_json().into_bytes();
+    buf.push(b'\n');
+    write.write_all(&buf).await?;
+  }
+}
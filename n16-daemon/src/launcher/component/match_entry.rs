@@ -1,18 +1,33 @@
 use iced::widget::{Space, button, image, row, svg, text};
 use iced::{Length, alignment};
-use n16_core::theme::{self, Base16Theme};
+use n16_core::{
+  config::StyleConfig,
+  theme::{self, Base16Theme},
+};
 
 use crate::launcher::providers::{Match, MatchIcon};
 
 use super::Component;
 
+/// Font sizes relative to the configured font size
+const TITLE_SCALE: f32 = 1.25;
+const DESCRIPTION_SCALE: f32 = 0.875;
+const PADDING: f32 = 5.0;
+
+/// Height of a match entry, used to compute scroll offsets
+pub fn height(style: &StyleConfig) -> f32 {
+  style.launcher().icon_size() + 2.0 * PADDING
+}
+
 pub fn view(
   match_entry: &Match,
   selected: bool,
+  confirmation: Option<String>,
+  style: &StyleConfig,
   on_press: crate::launcher::gui::Message,
 ) -> impl Into<Component> {
-  let image_size = 30;
-  let font_size = 20;
+  let image_size = style.launcher().icon_size();
+  let font_size = style.font_size() * TITLE_SCALE;
 
   let mut row = row![]
     .align_y(alignment::Vertical::Center)
@@ -34,29 +49,55 @@ pub fn view(
     }
   }
 
-  row = row.push(
-    text(match_entry.title.clone())
-      .align_y(alignment::Vertical::Center)
-      .height(image_size)
-      .size(font_size),
-  );
+  // A match waiting for confirmation shows the confirmation message instead of its title
+  if let Some(confirmation) = confirmation {
+    row = row.push(
+      text(confirmation)
+        .align_y(alignment::Vertical::Center)
+        .height(image_size)
+        .size(font_size)
+        .style(|theme: &Base16Theme| text::Style {
+          color: Some(theme.error()),
+        }),
+    );
+  } else {
+    row = row.push(
+      text(match_entry.title.clone())
+        .align_y(alignment::Vertical::Center)
+        .height(image_size)
+        .size(font_size),
+    );
+  }
+
+  if let Some(description) = &match_entry.description {
+    row = row.push(
+      text(description.clone())
+        .align_y(alignment::Vertical::Center)
+        .height(image_size)
+        .size(style.font_size() * DESCRIPTION_SCALE)
+        .style(|theme: &Base16Theme| text::Style {
+          color: Some(theme.foreground_muted()),
+        }),
+    );
+  }
 
   button(row)
-    .padding([5, 0])
+    .padding([PADDING, 0.0])
     .style(move |theme: &Base16Theme, status| {
+      // Unselected entries show the launcher background, which may be translucent
       let base = button::Style {
-        background: Some(theme.base00.into()),
+        background: None,
         ..theme::button::base(theme)
       };
 
       match status {
         _ if selected => button::Style {
-          background: Some(theme.base01.into()),
+          background: Some(theme.selection().into()),
           ..base
         },
 
         button::Status::Hovered => button::Style {
-          background: Some(theme.base01.into()),
+          background: Some(theme.selection().into()),
           ..base
         },
 
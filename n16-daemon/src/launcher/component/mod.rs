@@ -2,6 +2,7 @@ use iced::Element;
 use n16_core::theme::Base16Theme;
 
 pub mod match_entry;
+pub mod notice;
 pub mod provider_section;
 pub mod search;
 
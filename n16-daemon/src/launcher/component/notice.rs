@@ -0,0 +1,41 @@
+use iced::widget::{column, container, text};
+use n16_core::{config::StyleConfig, theme::Base16Theme};
+
+use super::Component;
+
+/// Body font size relative to the configured font size
+const BODY_SCALE: f32 = 0.875;
+
+/// Message shown by a provider between the search input and the matches
+pub fn view(title: &str, body: &str, is_error: bool, style: &StyleConfig) -> impl Into<Component> {
+  let mut content =
+    column![
+      text(title.to_string())
+        .size(style.font_size())
+        .style(move |theme: &Base16Theme| text::Style {
+          color: Some(if is_error {
+            theme.error()
+          } else {
+            theme.accent()
+          }),
+        })
+    ]
+    .spacing(2);
+
+  if !body.is_empty() {
+    content = content.push(
+      text(body.to_string())
+        .size(style.font_size() * BODY_SCALE)
+        .style(|theme: &Base16Theme| text::Style {
+          color: Some(theme.foreground_muted()),
+        }),
+    );
+  }
+
+  container(content)
+    .padding([4, 0])
+    .style(|theme: &Base16Theme| container::Style {
+      text_color: Some(theme.foreground()),
+      ..Default::default()
+    })
+}
@@ -1,19 +1,42 @@
+use iced::alignment;
 use iced::widget::{column, text};
+use n16_core::config::StyleConfig;
 
 use super::{Component, match_entry};
 use crate::launcher::providers::{Match, ProviderInfo};
 
+/// Height of the provider name shown above its matches, used to compute scroll offsets
+pub fn header_height(style: &StyleConfig) -> f32 {
+  style.font_size() * 1.5
+}
+
 pub fn view(
   provider_info: &ProviderInfo,
   matches: Vec<(usize, &Match)>,
   selected: Option<usize>,
+  confirmation: Option<(usize, &str)>,
+  style: &StyleConfig,
   on_press: impl Fn(usize) -> crate::launcher::gui::Message,
 ) -> impl Into<Component> {
-  let mut matches_veiw = column![text(provider_info.name.clone())];
+  let mut matches_veiw = column![
+    text(provider_info.name.clone())
+      .height(header_height(style))
+      .align_y(alignment::Vertical::Center)
+  ];
 
   for (idx, match_entry) in matches {
     let is_selected: bool = selected.is_some_and(|s| s == idx);
-    matches_veiw = matches_veiw.push(match_entry::view(match_entry, is_selected, on_press(idx)));
+    let confirmation = confirmation
+      .filter(|(confirm_idx, _)| *confirm_idx == idx)
+      .map(|(_, text)| text.to_string());
+
+    matches_veiw = matches_veiw.push(match_entry::view(
+      match_entry,
+      is_selected,
+      confirmation,
+      style,
+      on_press(idx),
+    ));
   }
 
   matches_veiw
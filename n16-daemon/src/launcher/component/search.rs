@@ -5,8 +5,9 @@ use crate::launcher::gui::Message;
 
 pub const SEARCH_INPUT_ID: &str = "SEARCH_QUERY_INPUT";
 
-pub fn view(query: &str) -> impl Into<Component> {
-  text_input("Search", query)
+pub fn view(query: &str, placeholder: Option<&str>, secure: bool) -> impl Into<Component> {
+  text_input(placeholder.unwrap_or("Search"), query)
     .id(SEARCH_INPUT_ID)
+    .secure(secure)
     .on_input(Message::SearchQueryChanged)
 }
@@ -1,66 +1,178 @@
-use std::{collections::HashMap, time::Duration};
+use std::{
+  collections::HashMap,
+  time::{Duration, Instant},
+};
 
 use iced::{
   Element, Length, Subscription, Task, gradient,
-  keyboard::key,
-  widget::{column, container, operation, rule, scrollable},
+  keyboard::{Key, Modifiers},
+  widget::{
+    column, container,
+    operation::{self, AbsoluteOffset, RelativeOffset},
+    rule, scrollable,
+    scrollable::Viewport,
+  },
+};
+use n16_core::{
+  config::{Keybinds, LauncherAction, LauncherConfig, StyleConfig},
+  theme::Base16Theme,
 };
-use n16_core::theme::{self, Base16Theme};
+use tokio_util::sync::CancellationToken;
 
 use crate::launcher::{
   Providers,
   component::{
-    provider_section,
+    match_entry, notice, provider_section,
     search::{self, SEARCH_INPUT_ID},
   },
-  providers::{ExecutionFinishAction, Match, Matches, ProviderId, ProviderInfo, ProviderType},
+  providers::{ExecutionFinishAction, ExecutionMode, Match, Matches, ProviderId, ProviderInfo},
 };
 
+const RESULTS_ID: &str = "LAUNCHER_RESULTS";
+
+/// Options for a single launcher window
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+  /// Initial search text
+  pub query: String,
+  /// Text shown in the empty search input
+  pub placeholder: Option<String>,
+  /// Hide the typed text
+  pub secure: bool,
+  /// Submit the typed text when running without any matches shown
+  pub submit_query: bool,
+  /// Also show static matches containing the characters of the search text in order
+  pub fuzzy: bool,
+}
+
 pub struct Launcher {
   query: String,
+  options: Options,
   selected_idx: (usize, usize),
+  viewport: Option<Viewport>,
+  config: LauncherConfig,
+  style: StyleConfig,
+  /// Cancelled when the search text changes, stopping the queries for the previous text
+  query_cancel: CancellationToken,
 
   providers: Providers,
   provider_info: Vec<ProviderInfo>,
-  matches: HashMap<ProviderId, Vec<Match>>,
+  static_matches: HashMap<ProviderId, Vec<Match>>,
+  dynamic_matches: HashMap<ProviderId, Vec<Match>>,
+  confirmation: Option<Confirmation>,
+  notice: Option<Notice>,
+  /// Provider views and search texts to go back to, see [`ExecutionFinishAction::PushProviderView`]
+  view_stack: Vec<(Vec<ProviderInfo>, String)>,
+}
+
+/// Message shown by a provider, see [`ExecutionFinishAction::ShowMessage`]
+struct Notice {
+  title: String,
+  body: String,
+  is_error: bool,
+}
+
+/// A match waiting to be run again to confirm its execution
+struct Confirmation {
+  idx: (usize, usize),
+  message: String,
+  deadline: Instant,
+}
+
+impl Confirmation {
+  /// Message shown in place of the match, with the seconds left to confirm
+  fn text(&self) -> String {
+    let remaining = self.deadline.saturating_duration_since(Instant::now());
+    format!("{} ({}s)", self.message, remaining.as_secs_f32().ceil())
+  }
+}
+
+/// Prompt to confirm `action` by pressing the first key bound to `run-selected` again
+fn confirmation_message(keybinds: &Keybinds, action: &str) -> String {
+  match keybinds.keys(LauncherAction::RunSelected).first() {
+    Some(key) => format!("Press {key} again to {action}"),
+    None => format!("Run again to {action}"),
+  }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Movement {
+  Prev,
+  Next,
+  PageUp,
+  PageDown,
+  First,
+  Last,
+  PrevSection,
+  NextSection,
 }
 
 #[derive(Debug)]
 pub enum Action {
   Task(Task<Message>),
   Close,
+  /// Submit the typed text, see [`Options::submit_query`]
+  SubmitQuery(String),
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
   Close,
   FocusInput,
-  SelectPrev,
-  SelectNext,
-  RunSelected,
+  KeyPressed(Key, Modifiers),
+  Scrolled(Viewport),
   RunIdx((usize, usize)),
   SearchQueryChanged(String),
   UpdateStaticMatches(Matches),
   UpdateDynamicMatches(String, Matches),
-  ProviderExecutionFinished(ExecutionFinishAction),
+  ProviderExecutionFinished((usize, usize), ExecutionFinishAction),
+  /// Update the confirmation countdown
+  ConfirmationTick,
 }
 
 impl Launcher {
-  pub fn new(mut providers: Providers) -> (Self, Task<Message>) {
+  pub fn new(
+    mut providers: Providers,
+    config: LauncherConfig,
+    style: StyleConfig,
+    options: Options,
+  ) -> (Self, Task<Message>) {
+    providers.start_session();
+
     let provider_task =
       Task::stream(providers.get_static_matches()).map(Message::UpdateStaticMatches);
 
-    (
-      Self {
-        query: String::new(),
-        selected_idx: (0, 0),
+    let query = options.query.clone();
+
+    let mut launcher = Self {
+      query: String::new(),
+      options,
+      selected_idx: (0, 0),
+      viewport: None,
+      config,
+      style,
+      query_cancel: CancellationToken::new(),
+
+      provider_info: providers.get_sorted_provider_info(),
+      providers,
+      static_matches: HashMap::new(),
+      dynamic_matches: HashMap::new(),
+      confirmation: None,
+      notice: None,
+      view_stack: Vec::new(),
+    };
 
-        provider_info: providers.get_sorted_provider_info(),
-        providers,
-        matches: HashMap::new(),
-      },
+    let query_task = if query.is_empty() {
+      Task::none()
+    } else {
+      launcher.update_query(&query)
+    };
+
+    (
+      launcher,
       Task::batch([
         provider_task,
+        query_task,
         Task::future(async {
           tokio::time::sleep(Duration::from_millis(250)).await;
           Message::FocusInput
@@ -69,161 +181,421 @@ impl Launcher {
     )
   }
 
+  /// Scroll the result list so the selected match is fully visible
+  fn scroll_to_selected(&self) -> Task<Message> {
+    let Some(viewport) = self.viewport else {
+      return Task::none();
+    };
+
+    let Some((row_top, is_first_in_section)) = self.selected_row_offset() else {
+      return Task::none();
+    };
+
+    // Keep the section header visible when selecting the first match of a section
+    let visible_top = if is_first_in_section {
+      row_top - provider_section::header_height(&self.style)
+    } else {
+      row_top
+    };
+    let row_bottom = row_top + match_entry::height(&self.style);
+
+    let offset = viewport.absolute_offset().y;
+    let height = viewport.bounds().height;
+
+    let new_offset = if visible_top < offset {
+      visible_top
+    } else if row_bottom > offset + height {
+      row_bottom - height
+    } else {
+      return Task::none();
+    };
+
+    operation::scroll_to(
+      RESULTS_ID,
+      AbsoluteOffset {
+        x: None,
+        y: Some(new_offset),
+      },
+    )
+  }
+
+  /// Vertical offset of the selected match from the top of the result list,
+  /// and whether it is the first match of its section
   #[expect(
-    clippy::unused_self,
-    reason = "Method is expected to use self in the future"
+    clippy::cast_precision_loss,
+    reason = "Match counts are far below the precision limit of f32"
   )]
-  fn scroll_to_selected(&self) -> Task<Message> {
-    Task::none() //operation::scroll_to(id, offset)
+  fn selected_row_offset(&self) -> Option<(f32, bool)> {
+    let selected = self.selection()?;
+    let header_height = provider_section::header_height(&self.style);
+    let match_height = match_entry::height(&self.style);
+    let mut offset = 0.0;
+
+    for (section_idx, visible) in self.visible_sections() {
+      offset += header_height;
+
+      if section_idx == selected.0 {
+        let position = visible.iter().position(|idx| *idx == selected.1)?;
+        return Some((offset + position as f32 * match_height, position == 0));
+      }
+
+      offset += visible.len() as f32 * match_height;
+    }
+
+    None
+  }
+
+  /// Number of matches that fit in the result list, used for paging
+  #[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    reason = "Viewport height is positive and small"
+  )]
+  fn page_size(&self) -> usize {
+    self.viewport.map_or(1, |viewport| {
+      ((viewport.bounds().height / match_entry::height(&self.style)) as usize).max(1)
+    })
   }
 
   fn update_query(&mut self, new_query: &str) -> Task<Message> {
     self.query.clear();
     self.query.push_str(new_query);
     self.selected_idx = (0, 0);
+    self.confirmation = None;
+    self.notice = None;
+
+    self.query_cancel.cancel();
+    self.query_cancel = CancellationToken::new();
+
+    let scroll_task = operation::snap_to(RESULTS_ID, RelativeOffset::START);
 
     if self.query.is_empty() {
-      for info in &self.provider_info {
-        if matches!(info.provider_type, ProviderType::Dynamic) {
-          self.matches.remove(&info.id);
-        }
-      }
+      self.dynamic_matches.clear();
 
-      Task::none()
+      scroll_task
     } else {
       let query = self.query.clone();
-      Task::stream(self.providers.get_dynamic_matches(query.clone()))
-        .map(move |matches| Message::UpdateDynamicMatches(query.clone(), matches))
+      Task::batch([
+        scroll_task,
+        Task::stream(self.providers.get_dynamic_matches(
+          query.clone(),
+          self.query_cancel.clone(),
+          self.config.search_debounce(),
+          self.config.provider_timeout(),
+        ))
+        .map(move |matches| Message::UpdateDynamicMatches(query.clone(), matches)),
+      ])
     }
   }
 
-  fn get_num_matches(&self, id: &str) -> usize {
-    self.matches.get(id).map_or(0, Vec::len)
+  fn static_matches(&self, id: &ProviderId) -> &[Match] {
+    self.static_matches.get(id).map_or(&[], Vec::as_slice)
   }
 
-  fn get_prev_idx(&self, from_idx: (usize, usize)) -> (usize, usize) {
-    if from_idx.1 != 0 {
-      return (from_idx.0, from_idx.1 - 1);
+  fn dynamic_matches(&self, id: &ProviderId) -> &[Match] {
+    self.dynamic_matches.get(id).map_or(&[], Vec::as_slice)
+  }
+
+  /// Match of a provider by index, static matches are indexed first followed by dynamic matches
+  fn provider_match(&self, id: &ProviderId, idx: usize) -> Option<&Match> {
+    let static_matches = self.static_matches(id);
+
+    match idx.checked_sub(static_matches.len()) {
+      Some(dynamic_idx) => self.dynamic_matches(id).get(dynamic_idx),
+      None => static_matches.get(idx),
     }
+  }
+
+  /// Indices of the matches shown for each provider, in display order.
+  /// Static matches filtered by the search text are shown before dynamic matches.
+  /// Providers without any shown matches are skipped.
+  fn visible_sections(&self) -> Vec<(usize, Vec<usize>)> {
+    self
+      .provider_info
+      .iter()
+      .enumerate()
+      .filter_map(|(section_idx, info)| {
+        let static_matches = self.static_matches(&info.id);
+        let dynamic_count = self.dynamic_matches(&info.id).len();
+
+        let mut scored: Vec<(usize, u8)> = static_matches
+          .iter()
+          .enumerate()
+          .filter_map(|(idx, m)| Some((idx, self.static_match_score(m)?)))
+          .collect();
+
+        // Stable sort keeps the provider's order within each score
+        scored.sort_by_key(|(_, score)| *score);
+
+        let visible: Vec<usize> = scored
+          .into_iter()
+          .map(|(idx, _)| idx)
+          .chain(static_matches.len()..static_matches.len() + dynamic_count)
+          .take(info.max_results.unwrap_or(usize::MAX))
+          .collect();
+
+        (!visible.is_empty()).then_some((section_idx, visible))
+      })
+      .collect()
+  }
 
-    let idx_0_len = self.provider_info.len();
-    let mut curr_idx_0 = usize::min(from_idx.0, idx_0_len);
+  fn visible_matches(&self) -> Vec<(usize, usize)> {
+    self
+      .visible_sections()
+      .into_iter()
+      .flat_map(|(section_idx, visible)| visible.into_iter().map(move |idx| (section_idx, idx)))
+      .collect()
+  }
 
-    for _ in 0..idx_0_len {
-      if curr_idx_0 == 0 {
-        curr_idx_0 = idx_0_len;
-      }
-      curr_idx_0 -= 1;
+  /// The selected match, or the first shown match if the selected one is hidden
+  fn selection(&self) -> Option<(usize, usize)> {
+    let visible = self.visible_matches();
 
-      match self.get_num_matches(&self.provider_info[curr_idx_0].id) {
-        0 => (),
-        idx_1_len => return (curr_idx_0, idx_1_len - 1),
-      }
+    if visible.contains(&self.selected_idx) {
+      Some(self.selected_idx)
+    } else {
+      visible.first().copied()
     }
+  }
+
+  fn move_selection(&mut self, movement: Movement) -> Task<Message> {
+    let visible = self.visible_matches();
+    let Some(last) = visible.len().checked_sub(1) else {
+      return Task::none();
+    };
+
+    let current = visible
+      .iter()
+      .position(|idx| *idx == self.selected_idx)
+      .unwrap_or(0);
+
+    let new = match movement {
+      Movement::Prev if current == 0 => last,
+      Movement::Prev => current - 1,
+      Movement::Next if current == last => 0,
+      Movement::Next => current + 1,
+      Movement::PageUp => current.saturating_sub(self.page_size()),
+      Movement::PageDown => usize::min(current + self.page_size(), last),
+      Movement::First => 0,
+      Movement::Last => last,
+      Movement::PrevSection => {
+        let section = visible[current].0;
+        let section_start = visible.iter().position(|idx| idx.0 == section).unwrap_or(0);
+
+        // Jump to the start of the current section first, then to the previous section
+        if current == section_start {
+          let prev = visible[if current == 0 { last } else { current - 1 }].0;
+          visible.iter().position(|idx| idx.0 == prev).unwrap_or(0)
+        } else {
+          section_start
+        }
+      }
+      Movement::NextSection => {
+        let section = visible[current].0;
+        visible
+          .iter()
+          .skip(current)
+          .position(|idx| idx.0 != section)
+          .map_or(0, |offset| current + offset)
+      }
+    };
 
-    (0, 0)
+    self.selected_idx = visible[new];
+    self.confirmation = None;
+    self.scroll_to_selected()
   }
 
-  fn get_next_idx(&self, from_idx: (usize, usize)) -> (usize, usize) {
-    let Some(info) = self.provider_info.get(from_idx.0) else {
-      return (0, 0);
+  fn handle_action(&mut self, action: LauncherAction) -> Action {
+    let task = match action {
+      LauncherAction::Close => match self.view_stack.pop() {
+        Some((provider_info, query)) => {
+          self.provider_info = provider_info;
+          Task::batch([
+            self.update_query(&query),
+            operation::move_cursor_to_end(SEARCH_INPUT_ID),
+          ])
+        }
+        None => return Action::Close,
+      },
+      LauncherAction::RunSelected => match self.selection() {
+        Some(idx) => self.run_match(idx, ExecutionMode::Default),
+        None if self.options.submit_query => return Action::SubmitQuery(self.query.clone()),
+        None => Task::none(),
+      },
+      LauncherAction::RunSelectedAlt => match self.selection() {
+        Some(idx) => self.run_match(idx, ExecutionMode::Alt),
+        None => Task::none(),
+      },
+      LauncherAction::SelectPrev => self.move_selection(Movement::Prev),
+      LauncherAction::SelectNext => self.move_selection(Movement::Next),
+      LauncherAction::SelectPageUp => self.move_selection(Movement::PageUp),
+      LauncherAction::SelectPageDown => self.move_selection(Movement::PageDown),
+      LauncherAction::SelectFirst => self.move_selection(Movement::First),
+      LauncherAction::SelectLast => self.move_selection(Movement::Last),
+      LauncherAction::SelectPrevSection => self.move_selection(Movement::PrevSection),
+      LauncherAction::SelectNextSection => self.move_selection(Movement::NextSection),
+      LauncherAction::QuickSelect(position) => match self.visible_matches().get(position) {
+        Some(idx) => self.run_match(*idx, ExecutionMode::Default),
+        None => Task::none(),
+      },
+    };
+
+    Action::Task(task)
+  }
+
+  fn run_match(&mut self, idx: (usize, usize), mode: ExecutionMode) -> Task<Message> {
+    // Running a match that is waiting for confirmation confirms it
+    let mode = match self.confirmation.take() {
+      Some(confirmation) if confirmation.idx == idx && confirmation.deadline > Instant::now() => {
+        ExecutionMode::Confirmed
+      }
+      _ => mode,
+    };
+
+    let Some((_, selected_match)) = self.get_match_at(idx) else {
+      return Task::none();
     };
 
-    if from_idx.1 <= self.get_num_matches(&info.id) {
-      return (from_idx.0, from_idx.1 + 1);
+    if !selected_match.executable {
+      return Task::none();
     }
 
-    let idx_0_len = self.provider_info.len();
-    let mut curr_idx_0 = from_idx.0;
+    Task::future(selected_match.clone().execute(mode))
+      .and_then(move |a| Task::done(Message::ProviderExecutionFinished(idx, a)))
+  }
 
-    for _ in 0..idx_0_len {
-      curr_idx_0 += 1;
-      if curr_idx_0 >= idx_0_len {
-        curr_idx_0 = 0;
+  fn handle_execution_finished(
+    &mut self,
+    idx: (usize, usize),
+    action: ExecutionFinishAction,
+  ) -> Task<Message> {
+    match action {
+      ExecutionFinishAction::Close => Task::done(Message::Close),
+      ExecutionFinishAction::KeepOpen => Task::none(),
+      ExecutionFinishAction::SetQuery(query) => Task::batch([
+        self.update_query(&query),
+        operation::move_cursor_to_end(SEARCH_INPUT_ID),
+      ]),
+      ExecutionFinishAction::RequestConfirmation { action, timeout } => {
+        self.selected_idx = idx;
+        self.confirmation = Some(Confirmation {
+          idx,
+          message: confirmation_message(self.config.keybinds(), &action),
+          deadline: Instant::now() + timeout,
+        });
+        Task::none()
       }
-
-      match self.get_num_matches(&self.provider_info[curr_idx_0].id) {
-        0 => (),
-        _ => return (curr_idx_0, 0),
+      ExecutionFinishAction::ReplaceMatches(matches) => {
+        let Some(info) = self.provider_info.get(idx.0) else {
+          return Task::none();
+        };
+
+        self.static_matches.insert(info.id.clone(), matches);
+        self.dynamic_matches.remove(&info.id);
+        self.selected_idx = (idx.0, 0);
+        Task::none()
+      }
+      ExecutionFinishAction::ShowMessage {
+        title,
+        body,
+        is_error,
+      } => {
+        self.notice = Some(Notice {
+          title,
+          body,
+          is_error,
+        });
+        Task::none()
       }
+      ExecutionFinishAction::PushProviderView(id) => self.push_provider_view(&id),
     }
+  }
+
+  fn push_provider_view(&mut self, id: &ProviderId) -> Task<Message> {
+    let Some(info) = self
+      .providers
+      .get_sorted_provider_info()
+      .into_iter()
+      .find(|info| info.id == *id)
+    else {
+      eprintln!("Can't show provider {id}, it isn't used by this launcher");
+      return Task::none();
+    };
 
-    (0, 0)
+    let provider_info = std::mem::replace(&mut self.provider_info, vec![info]);
+    self.view_stack.push((provider_info, self.query.clone()));
+
+    self.update_query("")
   }
 
   fn get_match_at(&self, idx: (usize, usize)) -> Option<(&String, &Match)> {
     let id = &self.provider_info.get(idx.0)?.id;
 
-    let matches = self.matches.get(id)?;
-    if idx.1 < matches.len() {
-      Some((id, &matches[idx.1]))
-    } else {
-      None
-    }
+    Some((id, self.provider_match(id, idx.1)?))
   }
 
-  fn filter_static_match(&self, static_match: &Match) -> bool {
-    let trimmed: String = self
-      .query
-      .split_whitespace()
-      .collect::<String>()
-      .to_lowercase();
-
-    static_match
-      .title
-      .split_whitespace()
-      .collect::<String>()
-      .to_lowercase()
-      .contains(&trimmed)
-      || static_match
-        .keywords
-        .iter()
-        .any(|kw| trimmed.contains(&kw.to_lowercase()))
+  fn static_match_score(&self, static_match: &Match) -> Option<u8> {
+    match_score(&self.query, static_match, self.options.fuzzy)
   }
 }
 
+/// How well a static match fits `query`, lower is better.
+/// Returns `None` if the match should be hidden.
+fn match_score<D>(query: &str, static_match: &Match<D>, fuzzy: bool) -> Option<u8> {
+  let trimmed: String = query.split_whitespace().collect::<String>().to_lowercase();
+
+  let title: String = static_match
+    .title
+    .split_whitespace()
+    .collect::<String>()
+    .to_lowercase();
+
+  if title.starts_with(&trimmed) {
+    Some(0)
+  } else if title.contains(&trimmed)
+    || static_match
+      .keywords
+      .iter()
+      .any(|kw| trimmed.contains(&kw.to_lowercase()))
+  {
+    Some(1)
+  } else if fuzzy && is_subsequence(&trimmed, &title) {
+    Some(2)
+  } else {
+    None
+  }
+}
+
+/// Whether all characters of `needle` appear in `haystack` in order
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+  let mut haystack = haystack.chars();
+  needle.chars().all(|c| haystack.any(|h| h == c))
+}
+
 impl Launcher {
   pub fn update(&mut self, message: Message) -> Action {
     let task = match message {
       Message::Close => return Action::Close,
 
-      Message::RunSelected => {
-        if let Some((id, selected_match)) = self.get_match_at(self.selected_idx) {
-          let p = self.providers.clone();
-          Task::future(p.execute_match((id.clone(), selected_match.clone())))
-            .and_then(|a| Task::done(Message::ProviderExecutionFinished(a)))
-        } else {
-          Task::none()
-        }
-      }
+      Message::KeyPressed(key, modifiers) => match self.config.keybinds().action(&key, modifiers) {
+        Some(action) => return self.handle_action(action),
+        None => Task::none(),
+      },
 
-      Message::RunIdx(idx) => {
-        if let Some((id, selected_match)) = self.get_match_at(idx) {
-          let p = self.providers.clone();
-          Task::future(p.execute_match((id.clone(), selected_match.clone())))
-            .and_then(|a| Task::done(Message::ProviderExecutionFinished(a)))
-        } else {
-          Task::none()
-        }
-      }
+      Message::RunIdx(idx) => self.run_match(idx, ExecutionMode::Default),
 
       Message::SearchQueryChanged(new_query) => self.update_query(&new_query),
 
-      Message::SelectPrev => {
-        self.selected_idx = self.get_prev_idx(self.selected_idx);
-        self.scroll_to_selected()
-      }
-
-      Message::SelectNext => {
-        self.selected_idx = self.get_next_idx(self.selected_idx);
-        self.scroll_to_selected()
+      Message::Scrolled(viewport) => {
+        self.viewport = Some(viewport);
+        Task::none()
       }
 
       Message::FocusInput => operation::focus(SEARCH_INPUT_ID),
 
       Message::UpdateStaticMatches(static_matches) => {
         self
-          .matches
+          .static_matches
           .insert(static_matches.id, static_matches.matches);
         Task::none()
       }
@@ -231,16 +603,28 @@ impl Launcher {
       Message::UpdateDynamicMatches(query, dynamic_matches) => {
         if query == self.query {
           self
-            .matches
+            .dynamic_matches
             .insert(dynamic_matches.id, dynamic_matches.matches);
         }
 
         Task::none()
       }
 
-      Message::ProviderExecutionFinished(action) => match action {
-        ExecutionFinishAction::Close => Task::done(Message::Close),
-      },
+      Message::ProviderExecutionFinished(idx, action) => {
+        self.handle_execution_finished(idx, action)
+      }
+
+      Message::ConfirmationTick => {
+        if self
+          .confirmation
+          .as_ref()
+          .is_some_and(|confirmation| confirmation.deadline <= Instant::now())
+        {
+          self.confirmation = None;
+        }
+
+        Task::none()
+      }
     };
 
     Action::Task(task)
@@ -248,58 +632,78 @@ impl Launcher {
 
   pub fn view(&self) -> Element<'_, Message, Base16Theme> {
     let mut provider_sections = column![];
-
-    for (idx, info) in self.provider_info.iter().enumerate() {
-      let Some(matches) = self.matches.get(&info.id) else {
-        continue;
-      };
-
-      let matches = matches.iter().enumerate();
-      let matches: Vec<(usize, &Match)> = if matches!(info.provider_type, ProviderType::Static) {
-        matches
-          .filter(|(_, m)| self.filter_static_match(m))
-          .collect()
-      } else {
-        matches.collect()
-      };
-
-      if matches.is_empty() {
-        continue;
-      }
-
-      let selected = if idx == self.selected_idx.0 {
-        Some(self.selected_idx.1)
-      } else {
-        None
-      };
-
-      provider_sections =
-        provider_sections.push(provider_section::view(info, matches, selected, |sub_idx| {
-          Message::RunIdx((idx, sub_idx))
-        }));
+    let selection = self.selection();
+
+    for (section_idx, visible) in self.visible_sections() {
+      let info = &self.provider_info[section_idx];
+      let matches: Vec<(usize, &Match)> = visible
+        .into_iter()
+        .filter_map(|idx| Some((idx, self.provider_match(&info.id, idx)?)))
+        .collect();
+
+      let selected = selection
+        .filter(|selected| selected.0 == section_idx)
+        .map(|selected| selected.1);
+
+      let confirmation = self
+        .confirmation
+        .as_ref()
+        .filter(|confirmation| confirmation.idx.0 == section_idx)
+        .map(|confirmation| (confirmation.idx.1, confirmation.text()));
+      let confirmation = confirmation
+        .as_ref()
+        .map(|(idx, text)| (*idx, text.as_str()));
+
+      provider_sections = provider_sections.push(provider_section::view(
+        info,
+        matches,
+        selected,
+        confirmation,
+        &self.style,
+        |sub_idx| Message::RunIdx((section_idx, sub_idx)),
+      ));
     }
 
     let column = column![
-      search::view(&self.query).into(),
-      column![rule::horizontal(1).style(|theme: &Base16Theme| theme::rule::colored(theme.base02))]
-        .height(20),
+      search::view(
+        &self.query,
+        self.options.placeholder.as_deref(),
+        self.options.secure
+      )
+      .into(),
+      self.notice.as_ref().map(|notice| notice::view(
+        &notice.title,
+        &notice.body,
+        notice.is_error,
+        &self.style
+      )
+      .into()),
+      column![rule::horizontal(1)].height(20),
       scrollable(provider_sections)
+        .id(RESULTS_ID)
+        .on_scroll(Message::Scrolled)
     ];
 
     let inner = container(column)
       .height(Length::Fill)
-      .padding(8)
+      .padding(self.style.launcher().padding())
       .style(|theme| container::Style {
-        background: Some(theme.base00.into()),
+        background: Some(
+          theme
+            .background()
+            .scale_alpha(theme.style.background_opacity)
+            .into(),
+        ),
         ..Default::default()
       });
 
     container(inner)
       .padding(4)
       .style(|theme| {
+        let opacity = theme.style.background_opacity;
         let gradient = gradient::Linear::new(50)
-          .add_stop(0.0, theme.base0D)
-          .add_stop(1.0, theme.base0E);
+          .add_stop(0.0, theme.accent().scale_alpha(opacity))
+          .add_stop(1.0, theme.accent_secondary().scale_alpha(opacity));
 
         container::Style {
           background: Some(gradient.into()),
@@ -309,21 +713,19 @@ impl Launcher {
       .into()
   }
 
-  #[expect(
-    clippy::unused_self,
-    reason = "Method is expected to use self in the future"
-  )]
   pub fn subscription(&self, window_id: iced::window::Id) -> Subscription<Message> {
-    iced::event::listen_with(|event, _, id| {
+    let confirmation_tick = if self.confirmation.is_some() {
+      iced::time::every(Duration::from_millis(250)).map(|_| Message::ConfirmationTick)
+    } else {
+      Subscription::none()
+    };
+
+    let events = iced::event::listen_with(|event, _, id| {
       (match event {
         iced::Event::Window(iced::window::Event::Unfocused) => Some(Message::Close),
-        iced::Event::Keyboard(iced::keyboard::Event::KeyPressed { key, .. }) => match key {
-          iced::keyboard::Key::Named(key::Named::ArrowUp) => Some(Message::SelectPrev),
-          iced::keyboard::Key::Named(key::Named::ArrowDown) => Some(Message::SelectNext),
-          iced::keyboard::Key::Named(key::Named::Enter) => Some(Message::RunSelected),
-          iced::keyboard::Key::Named(key::Named::Escape) => Some(Message::Close),
-          _ => None,
-        },
+        iced::Event::Keyboard(iced::keyboard::Event::KeyPressed { key, modifiers, .. }) => {
+          Some(Message::KeyPressed(key, modifiers))
+        }
         _ => None,
       })
       .map(|m| (m, id))
@@ -335,6 +737,52 @@ impl Launcher {
       } else {
         None
       }
-    })
+    });
+
+    Subscription::batch([events, confirmation_tick])
+  }
+}
+
+impl Drop for Launcher {
+  fn drop(&mut self) {
+    self.query_cancel.cancel();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn titled(title: &str) -> Match<()> {
+    Match {
+      title: title.into(),
+      description: None,
+      icon: None,
+      keywords: vec!["browser".into()],
+      executable: true,
+      data: (),
+    }
+  }
+
+  #[test]
+  fn prefix_ranks_before_substring_and_keyword() {
+    assert_eq!(match_score("fire", &titled("Firefox"), false), Some(0));
+    assert_eq!(match_score("fox", &titled("Firefox"), false), Some(1));
+    assert_eq!(match_score("browser", &titled("Firefox"), false), Some(1));
+  }
+
+  #[test]
+  fn confirmation_names_the_run_selected_key() {
+    assert_eq!(
+      confirmation_message(&Keybinds::default(), "Shut Down"),
+      "Press Enter again to Shut Down"
+    );
+  }
+
+  #[test]
+  fn subsequences_only_match_when_fuzzy() {
+    assert_eq!(match_score("ffx", &titled("Firefox"), false), None);
+    assert_eq!(match_score("ffx", &titled("Firefox"), true), Some(2));
+    assert_eq!(match_score("xff", &titled("Firefox"), true), None);
   }
 }
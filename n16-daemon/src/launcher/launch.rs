@@ -0,0 +1,224 @@
+use std::{
+  env,
+  ffi::OsStr,
+  io,
+  os::unix::fs::PermissionsExt,
+  path::{Path, PathBuf},
+  process::Stdio,
+};
+
+use n16_core::config::LaunchBackend;
+use n16_ipc::Event;
+use tokio::process::Command;
+
+use crate::{events, launcher::providers::ExecutionFinishAction};
+
+mod activation;
+mod dbus_activation;
+mod systemd;
+
+pub use activation::set_connection;
+
+/// An application to launch, see [`launch_app`]
+pub struct AppLaunch<'a> {
+  pub name: &'a str,
+  /// Desktop file id, such as `org.gnome.Nautilus`
+  pub id: &'a str,
+  /// Program and arguments from the `Exec` key, empty if the entry has none
+  pub command: Vec<&'a str>,
+  /// Whether the entry has `DBusActivatable=true`
+  pub dbus_activatable: bool,
+}
+
+/// Path of the executable `program`, looked up in `PATH` unless it contains a `/`
+fn find_program(program: &OsStr) -> io::Result<PathBuf> {
+  let is_executable = |path: &Path| {
+    path
+      .metadata()
+      .is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+  };
+
+  let path = Path::new(program);
+  let found = if path.components().count() > 1 {
+    is_executable(path).then(|| path.to_path_buf())
+  } else {
+    env::var_os("PATH").and_then(|paths| {
+      env::split_paths(&paths)
+        .map(|dir| dir.join(program))
+        .find(|path| is_executable(path))
+    })
+  };
+
+  found.ok_or_else(|| {
+    io::Error::new(
+      io::ErrorKind::NotFound,
+      format!("{} not found", path.display()),
+    )
+  })
+}
+
+/// Spawn `command` through `setsid` so it runs in a new session. It outlives the daemon, has no
+/// controlling terminal and doesn't receive signals sent to the daemon's process group.
+///
+/// `setsid` execs the program in place, so the child is waited on in the background and never left
+/// as a zombie. The program is looked up first, since `setsid` would only fail after spawning.
+/// The activation token is passed in `XDG_ACTIVATION_TOKEN` and `DESKTOP_STARTUP_ID`.
+/// Returns the process id of the child.
+pub fn spawn_detached<S: AsRef<OsStr>>(
+  command: &[S],
+  activation_token: Option<&str>,
+) -> io::Result<u32> {
+  let Some((program, args)) = command.split_first() else {
+    return Err(io::Error::new(io::ErrorKind::InvalidInput, "empty command"));
+  };
+
+  // Not a process group leader, so `setsid` doesn't fork and keeps the process id
+  let mut command = Command::new("setsid");
+  command
+    .arg(find_program(program.as_ref())?)
+    .args(args)
+    .stdin(Stdio::null())
+    .stdout(Stdio::null())
+    .stderr(Stdio::null());
+
+  if let Some(token) = activation_token {
+    command
+      .env("XDG_ACTIVATION_TOKEN", token)
+      .env("DESKTOP_STARTUP_ID", token);
+  }
+
+  let mut child = command.spawn()?;
+
+  let pid = child
+    .id()
+    .ok_or_else(|| io::Error::other("child exited before it could be waited on"))?;
+  let program = program.as_ref().to_string_lossy().into_owned();
+
+  tokio::spawn(async move {
+    match child.wait().await {
+      Ok(status) if !status.success() => eprintln!("{program} exited with {status}"),
+      Ok(_) => {}
+      Err(error) => eprintln!("Failed to wait for {program}: {error}"),
+    }
+  });
+
+  Ok(pid)
+}
+
+/// Launch `command`, closing the launcher on success.
+/// Failures are shown in the launcher and sent as [`Event::LaunchFailed`].
+pub fn launch<D, S: AsRef<OsStr>>(name: &str, command: &[S]) -> ExecutionFinishAction<D> {
+  match spawn_detached(command, None) {
+    Ok(_) => launched(name, None),
+    Err(error) => launch_failed(name, &error),
+  }
+}
+
+/// Launch an application, like [`launch`].
+///
+/// D-Bus activatable applications are activated on the session bus, falling back to their `Exec`
+/// key if activation fails. Other applications are spawned with `backend`, and keep running as
+/// children of the daemon if they can't be moved into a scope.
+/// Either way, the application receives an activation token if one can be requested.
+pub async fn launch_app<D>(
+  app: &AppLaunch<'_>,
+  backend: LaunchBackend,
+) -> ExecutionFinishAction<D> {
+  let activation_token = activation::request_token(app.id).await;
+
+  if app.dbus_activatable {
+    let result = match zbus::Connection::session().await {
+      Ok(connection) => {
+        dbus_activation::activate(&connection, app.id, activation_token.as_deref()).await
+      }
+      Err(error) => Err(error),
+    };
+
+    match result {
+      Ok(()) => return launched(app.name, activation_token),
+      Err(error) if app.command.is_empty() => {
+        return launch_failed(app.name, &io::Error::other(error));
+      }
+      Err(error) => eprintln!("Failed to activate {}, using Exec: {error}", app.id),
+    }
+  }
+
+  let name = app.name;
+  let pid = match spawn_detached(&app.command, activation_token.as_deref()) {
+    Ok(pid) => pid,
+    Err(error) => return launch_failed(name, &error),
+  };
+
+  if backend == LaunchBackend::SystemdScope {
+    let unit_name = systemd::scope_name(app.id);
+
+    let result = match zbus::Connection::session().await {
+      Ok(connection) => systemd::start_scope(&connection, &unit_name, pid).await,
+      Err(error) => Err(error),
+    };
+
+    if let Err(error) = result {
+      eprintln!("Failed to move {name} into {unit_name}: {error}");
+    }
+  }
+
+  launched(name, activation_token)
+}
+
+/// Report a successful launch over IPC and close the launcher
+fn launched<D>(name: &str, activation_token: Option<String>) -> ExecutionFinishAction<D> {
+  events::send(Event::Launched {
+    name: name.into(),
+    activation_token,
+  });
+
+  ExecutionFinishAction::Close
+}
+
+/// Report a failed launch in the launcher and over IPC
+pub fn launch_failed<D>(name: &str, error: &io::Error) -> ExecutionFinishAction<D> {
+  eprintln!("Failed to launch {name}: {error}");
+
+  events::send(Event::LaunchFailed {
+    name: name.into(),
+    error: error.to_string(),
+  });
+
+  ExecutionFinishAction::ShowMessage {
+    title: format!("Failed to launch {name}"),
+    body: error.to_string(),
+    is_error: true,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::time::Duration;
+
+  use rustix::process::{Pid, getsid};
+
+  use super::*;
+
+  #[tokio::test]
+  async fn spawned_child_leads_its_own_session() {
+    let pid = spawn_detached(&["sleep", "1"], None).unwrap();
+    let pid = Pid::from_raw(pid.try_into().unwrap()).unwrap();
+
+    // The session is only created once `setsid` runs in the child
+    for _ in 0..50 {
+      if getsid(Some(pid)).unwrap() == pid {
+        break;
+      }
+      tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    assert_eq!(getsid(Some(pid)).unwrap(), pid);
+    assert_ne!(getsid(None).unwrap(), pid);
+  }
+
+  #[test]
+  fn missing_program_fails_to_spawn() {
+    let error = spawn_detached(&["n16-no-such-program"], None).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::NotFound);
+  }
+}
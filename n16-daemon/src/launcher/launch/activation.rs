@@ -0,0 +1,350 @@
+use std::{
+  error::Error,
+  io,
+  os::fd::{AsRawFd, RawFd},
+  sync::OnceLock,
+  time::Duration,
+};
+
+use tokio::{
+  io::{Interest, unix::AsyncFd},
+  sync::Mutex,
+};
+use wayland_client::{
+  Connection, Dispatch, EventQueue, Proxy, QueueHandle, WEnum,
+  backend::WaylandError,
+  protocol::{
+    wl_callback::{self, WlCallback},
+    wl_keyboard::{self, WlKeyboard},
+    wl_registry::{self, WlRegistry},
+    wl_seat::{self, WlSeat},
+    wl_surface::WlSurface,
+  },
+};
+use wayland_protocols::xdg::activation::v1::client::{
+  xdg_activation_token_v1::{self, XdgActivationTokenV1},
+  xdg_activation_v1::XdgActivationV1,
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error + Send + Sync>>;
+
+/// Time the compositor is given to send the token before launching without one
+const TOKEN_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Interval at which events read by other threads are dispatched
+const DISPATCH_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Highest `wl_seat` version with requests or events used here
+const SEAT_VERSION: u32 = 5;
+
+/// The connection shared with `iced_layershell`, see [`set_connection`]
+static CONNECTION: OnceLock<Connection> = OnceLock::new();
+
+/// Globals bound on [`CONNECTION`], created by the first token request
+static ACTIVATOR: Mutex<Option<Activator>> = Mutex::const_new(None);
+
+/// Request tokens on the connection the launcher's surfaces are created on, since tokens can only
+/// be tied to the launcher's surface on it
+pub fn set_connection(connection: Connection) {
+  let _ = CONNECTION.set(connection);
+}
+
+#[derive(Default)]
+struct State {
+  /// Registry name and version of the first seat
+  seat: Option<(u32, u32)>,
+  /// Registry name of `xdg_activation_v1`
+  activation: Option<u32>,
+  has_keyboard: bool,
+  /// Serial and surface of the keyboard focus, sent when a keyboard is created
+  focus: Option<(u32, WlSurface)>,
+  synced: bool,
+  pending_token: Option<XdgActivationTokenV1>,
+  token: Option<String>,
+}
+
+impl Dispatch<WlRegistry, ()> for State {
+  fn event(
+    state: &mut Self,
+    _: &WlRegistry,
+    event: wl_registry::Event,
+    (): &(),
+    _: &Connection,
+    _: &QueueHandle<Self>,
+  ) {
+    if let wl_registry::Event::Global {
+      name,
+      interface,
+      version,
+    } = event
+    {
+      if interface == WlSeat::interface().name && state.seat.is_none() {
+        state.seat = Some((name, version));
+      } else if interface == XdgActivationV1::interface().name {
+        state.activation = Some(name);
+      }
+    }
+  }
+}
+
+impl Dispatch<WlCallback, ()> for State {
+  fn event(
+    state: &mut Self,
+    _: &WlCallback,
+    event: wl_callback::Event,
+    (): &(),
+    _: &Connection,
+    _: &QueueHandle<Self>,
+  ) {
+    if let wl_callback::Event::Done { .. } = event {
+      state.synced = true;
+    }
+  }
+}
+
+impl Dispatch<WlSeat, ()> for State {
+  fn event(
+    state: &mut Self,
+    _: &WlSeat,
+    event: wl_seat::Event,
+    (): &(),
+    _: &Connection,
+    _: &QueueHandle<Self>,
+  ) {
+    if let wl_seat::Event::Capabilities {
+      capabilities: WEnum::Value(capabilities),
+    } = event
+    {
+      state.has_keyboard = capabilities.contains(wl_seat::Capability::Keyboard);
+    }
+  }
+}
+
+impl Dispatch<WlKeyboard, ()> for State {
+  fn event(
+    state: &mut Self,
+    keyboard: &WlKeyboard,
+    event: wl_keyboard::Event,
+    (): &(),
+    _: &Connection,
+    _: &QueueHandle<Self>,
+  ) {
+    // The keyboard only exists to receive the focus, further events aren't needed
+    if let wl_keyboard::Event::Enter {
+      serial, surface, ..
+    } = event
+    {
+      state.focus = Some((serial, surface));
+
+      if keyboard.version() >= 3 {
+        keyboard.release();
+      }
+    }
+  }
+}
+
+impl Dispatch<XdgActivationV1, ()> for State {
+  fn event(
+    _: &mut Self,
+    _: &XdgActivationV1,
+    _: <XdgActivationV1 as Proxy>::Event,
+    (): &(),
+    _: &Connection,
+    _: &QueueHandle<Self>,
+  ) {
+  }
+}
+
+impl Dispatch<XdgActivationTokenV1, ()> for State {
+  fn event(
+    state: &mut Self,
+    token: &XdgActivationTokenV1,
+    event: xdg_activation_token_v1::Event,
+    (): &(),
+    _: &Connection,
+    _: &QueueHandle<Self>,
+  ) {
+    // Tokens of requests that timed out may still arrive, and are dropped
+    if let xdg_activation_token_v1::Event::Done { token: value } = event {
+      if state.pending_token.as_ref() == Some(token) {
+        state.pending_token = None;
+        state.token = Some(value);
+      }
+
+      token.destroy();
+    }
+  }
+}
+
+/// An event queue on the launcher's connection, dispatched only while requesting a token
+struct Events {
+  connection: Connection,
+  queue: EventQueue<State>,
+  fd: AsyncFd<RawFd>,
+  state: State,
+}
+
+impl Events {
+  fn new(connection: Connection) -> Result<Self> {
+    let fd = AsyncFd::with_interest(
+      connection.backend().poll_fd().as_raw_fd(),
+      Interest::READABLE,
+    )?;
+
+    Ok(Self {
+      queue: connection.new_event_queue(),
+      connection,
+      fd,
+      state: State::default(),
+    })
+  }
+
+  /// Dispatch events until `done` returns true.
+  ///
+  /// Other threads read from the same connection, and events they read for this queue don't wake
+  /// it, so the queue is checked again after [`DISPATCH_INTERVAL`] even if nothing is readable.
+  async fn dispatch_until(&mut self, done: impl Fn(&State) -> bool) -> Result<()> {
+    loop {
+      self.queue.dispatch_pending(&mut self.state)?;
+      if done(&self.state) {
+        return Ok(());
+      }
+
+      self.connection.flush()?;
+
+      let Ok(ready) = tokio::time::timeout(DISPATCH_INTERVAL, self.fd.readable()).await else {
+        continue;
+      };
+      let mut ready = ready?;
+
+      // Events for this queue were read in the meantime
+      let Some(guard) = self.queue.prepare_read() else {
+        continue;
+      };
+
+      match guard.read() {
+        Ok(_) => {}
+        Err(WaylandError::Io(error)) if error.kind() == io::ErrorKind::WouldBlock => {
+          ready.clear_ready();
+        }
+        Err(error) => return Err(error.into()),
+      }
+    }
+  }
+
+  async fn roundtrip(&mut self) -> Result<()> {
+    self.state.synced = false;
+    self.connection.display().sync(&self.queue.handle(), ());
+    self.dispatch_until(|state| state.synced).await
+  }
+}
+
+/// The globals needed to request tokens, bound once on the launcher's connection
+struct Activator {
+  events: Events,
+  activation: XdgActivationV1,
+  seat: Option<WlSeat>,
+}
+
+impl Activator {
+  async fn new(connection: Connection) -> Result<Self> {
+    let mut events = Events::new(connection)?;
+    let qh = events.queue.handle();
+
+    let registry = events.connection.display().get_registry(&qh, ());
+    events.roundtrip().await?;
+
+    let activation = registry.bind(
+      events
+        .state
+        .activation
+        .ok_or("The compositor doesn't support xdg_activation_v1")?,
+      1,
+      &qh,
+      (),
+    );
+    let seat = events
+      .state
+      .seat
+      .map(|(name, version)| registry.bind(name, version.min(SEAT_VERSION), &qh, ()));
+
+    // Receive the seat's capabilities
+    events.roundtrip().await?;
+
+    Ok(Self {
+      events,
+      activation,
+      seat,
+    })
+  }
+
+  async fn request(&mut self, app_id: &str) -> Result<String> {
+    let qh = self.events.queue.handle();
+    let token = self.activation.get_activation_token(&qh, ());
+    self.events.state.pending_token = Some(token.clone());
+    self.events.state.token = None;
+
+    // A new keyboard is entered with a fresh serial if one of the launcher's surfaces has focus
+    if let Some(seat) = &self.seat
+      && self.events.state.has_keyboard
+    {
+      self.events.state.focus = None;
+      let keyboard = seat.get_keyboard(&qh, ());
+      self.events.roundtrip().await?;
+
+      match self.events.state.focus.take() {
+        Some((serial, surface)) => {
+          token.set_serial(serial, seat);
+          token.set_surface(&surface);
+        }
+        None if keyboard.version() >= 3 => keyboard.release(),
+        None => {}
+      }
+    }
+
+    token.set_app_id(app_id.into());
+    token.commit();
+
+    self
+      .events
+      .dispatch_until(|state| state.token.is_some())
+      .await?;
+
+    Ok(self.events.state.token.take().unwrap_or_default())
+  }
+}
+
+/// Request an `xdg_activation_v1` token for launching `app_id`, so the compositor can focus the new
+/// window. Waits for the compositor's reply for at most [`TOKEN_TIMEOUT`].
+///
+/// The token is tied to the launcher's focused surface and a serial of the seat's keyboard, so
+/// compositors that require them to pass focus along accept it.
+pub async fn request_token(app_id: &str) -> Option<String> {
+  let Some(connection) = CONNECTION.get() else {
+    eprintln!("The launcher isn't connected to the compositor, launching without a token");
+    return None;
+  };
+
+  let mut activator = ACTIVATOR.lock().await;
+
+  let request = async {
+    let activator = match &mut *activator {
+      Some(activator) => activator,
+      None => activator.insert(Activator::new(connection.clone()).await?),
+    };
+
+    activator.request(app_id).await
+  };
+
+  match tokio::time::timeout(TOKEN_TIMEOUT, request).await {
+    Ok(Ok(token)) => Some(token),
+    Ok(Err(error)) => {
+      eprintln!("Failed to request an activation token: {error}");
+      None
+    }
+    Err(_) => {
+      eprintln!("The compositor didn't send an activation token in time, launching without one");
+      None
+    }
+  }
+}
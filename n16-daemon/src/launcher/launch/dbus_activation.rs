@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use zbus::{Connection, zvariant::Value};
+
+const INTERFACE: &str = "org.freedesktop.Application";
+
+/// Object path of a D-Bus activatable application, derived from its desktop file id as described
+/// in the desktop entry specification
+pub fn object_path(app_id: &str) -> String {
+  let path: String = app_id
+    .chars()
+    .map(|c| match c {
+      '.' => '/',
+      '-' => '_',
+      c => c,
+    })
+    .collect();
+
+  format!("/{path}")
+}
+
+/// Call `org.freedesktop.Application.Activate` on the application `app_id`, which starts it through
+/// D-Bus activation if it isn't running
+pub async fn activate(
+  connection: &Connection,
+  app_id: &str,
+  activation_token: Option<&str>,
+) -> zbus::Result<()> {
+  let mut platform_data: HashMap<&str, Value> = HashMap::new();
+
+  if let Some(token) = activation_token {
+    platform_data.insert("activation-token", Value::from(token));
+    platform_data.insert("desktop-startup-id", Value::from(token));
+  }
+
+  connection
+    .call_method(
+      Some(app_id),
+      object_path(app_id).as_str(),
+      Some(INTERFACE),
+      "Activate",
+      &(platform_data,),
+    )
+    .await?;
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::{Arc, Mutex};
+
+  use zbus::zvariant::OwnedValue;
+
+  use super::*;
+  use crate::test_bus::TestBus;
+
+  #[derive(Default)]
+  struct StubApplication {
+    /// Platform data of every `Activate` call
+    activations: Arc<Mutex<Vec<HashMap<String, OwnedValue>>>>,
+  }
+
+  #[zbus::interface(name = "org.freedesktop.Application")]
+  impl StubApplication {
+    fn activate(&self, platform_data: HashMap<String, OwnedValue>) {
+      self.activations.lock().unwrap().push(platform_data);
+    }
+  }
+
+  #[test]
+  fn object_path_escapes_app_id() {
+    assert_eq!(object_path("org.gnome.Nautilus"), "/org/gnome/Nautilus");
+    assert_eq!(object_path("org.example.my-app"), "/org/example/my_app");
+  }
+
+  #[tokio::test]
+  async fn activate_passes_activation_token() {
+    let bus = TestBus::start();
+    let app_id = "org.example.my-app";
+    let application = StubApplication::default();
+    let activations = Arc::clone(&application.activations);
+    let _application = bus.serve(app_id, &object_path(app_id), application).await;
+    let connection = bus.connect().await;
+
+    activate(&connection, app_id, Some("token-1"))
+      .await
+      .unwrap();
+    activate(&connection, app_id, None).await.unwrap();
+
+    let activations = activations.lock().unwrap();
+    assert_eq!(activations.len(), 2);
+
+    let token = |key: &str| String::try_from(activations[0][key].try_clone().unwrap()).unwrap();
+    assert_eq!(token("activation-token"), "token-1");
+    assert_eq!(token("desktop-startup-id"), "token-1");
+    assert!(activations[1].is_empty());
+  }
+}
@@ -0,0 +1,146 @@
+use std::{
+  collections::hash_map::RandomState,
+  hash::{BuildHasher, Hasher},
+};
+
+use zbus::{Connection, zvariant::Value};
+
+const DESTINATION: &str = "org.freedesktop.systemd1";
+const PATH: &str = "/org/freedesktop/systemd1";
+const INTERFACE: &str = "org.freedesktop.systemd1.Manager";
+
+/// Name of the launcher in unit names, see
+/// <https://systemd.io/DESKTOP_ENVIRONMENTS/#xdg-standardization-for-applications>
+const LAUNCHER_NAME: &str = "n16";
+
+/// Escape a string for use in a unit name, keeping only characters valid in unit names.
+/// `-` is escaped too, since it separates the parts of the name.
+fn escape(s: &str) -> String {
+  s.bytes()
+    .map(|byte| {
+      if byte.is_ascii_alphanumeric() || matches!(byte, b':' | b'_' | b'.') {
+        char::from(byte).to_string()
+      } else {
+        format!("\\x{byte:02x}")
+      }
+    })
+    .collect()
+}
+
+/// Unit name following the XDG convention `app-<launcher>-<app id>-<random>.scope`
+pub fn scope_name(app_id: &str) -> String {
+  // A randomly keyed hasher gives a random value without depending on a random number crate
+  let random = RandomState::new().build_hasher().finish();
+
+  format!("app-{LAUNCHER_NAME}-{}-{random:x}.scope", escape(app_id))
+}
+
+/// Move the running process `pid` into a new transient scope called `unit_name` with systemd's
+/// `StartTransientUnit`
+pub async fn start_scope(connection: &Connection, unit_name: &str, pid: u32) -> zbus::Result<()> {
+  let properties: Vec<(&str, Value)> = vec![
+    ("PIDs", Value::from(vec![pid])),
+    ("CollectMode", Value::from("inactive-or-failed")),
+  ];
+  let aux: Vec<(&str, Vec<(&str, Value)>)> = Vec::new();
+
+  connection
+    .call_method(
+      Some(DESTINATION),
+      PATH,
+      Some(INTERFACE),
+      "StartTransientUnit",
+      &(unit_name, "fail", properties, aux),
+    )
+    .await?;
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::{Arc, Mutex};
+
+  use zbus::zvariant::{OwnedObjectPath, OwnedValue};
+
+  use super::*;
+  use crate::test_bus::TestBus;
+
+  type Properties = Vec<(String, OwnedValue)>;
+  type Unit = (String, String, Properties, Vec<(String, Properties)>);
+
+  #[derive(Default)]
+  struct MockSystemd {
+    /// Arguments of every `StartTransientUnit` call
+    units: Arc<Mutex<Vec<Unit>>>,
+  }
+
+  #[zbus::interface(name = "org.freedesktop.systemd1.Manager")]
+  impl MockSystemd {
+    fn start_transient_unit(
+      &self,
+      name: String,
+      mode: String,
+      properties: Properties,
+      aux: Vec<(String, Properties)>,
+    ) -> OwnedObjectPath {
+      self
+        .units
+        .lock()
+        .unwrap()
+        .push((name, mode, properties, aux));
+      OwnedObjectPath::try_from("/org/freedesktop/systemd1/job/1").unwrap()
+    }
+  }
+
+  #[test]
+  fn scope_name_follows_xdg_convention() {
+    let name = scope_name("org.gnome.Nautilus");
+    let random = name
+      .strip_prefix("app-n16-org.gnome.Nautilus-")
+      .and_then(|rest| rest.strip_suffix(".scope"))
+      .unwrap();
+
+    assert!(!random.is_empty());
+    assert!(random.chars().all(|c| c.is_ascii_hexdigit()));
+    assert_ne!(scope_name("org.gnome.Nautilus"), name);
+  }
+
+  #[test]
+  fn scope_name_escapes_separators() {
+    assert!(scope_name("my-app@home").starts_with(r"app-n16-my\x2dapp\x40home-"));
+  }
+
+  #[tokio::test]
+  async fn start_scope_moves_pid_into_unit() {
+    let bus = TestBus::start();
+    let systemd = MockSystemd::default();
+    let units = Arc::clone(&systemd.units);
+    let _systemd = bus.serve(DESTINATION, PATH, systemd).await;
+    let connection = bus.connect().await;
+
+    start_scope(&connection, "app-n16-foot-1.scope", 1234)
+      .await
+      .unwrap();
+
+    let mut units = units.lock().unwrap();
+    let (name, mode, properties, aux) = units.pop().unwrap();
+    assert!(units.is_empty());
+    assert_eq!(name, "app-n16-foot-1.scope");
+    assert_eq!(mode, "fail");
+    assert!(aux.is_empty());
+
+    let property = |key: &str| {
+      properties
+        .iter()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value.try_clone().unwrap())
+        .unwrap()
+    };
+    assert_eq!(Vec::<u32>::try_from(property("PIDs")).unwrap(), [1234]);
+    assert_eq!(
+      String::try_from(property("CollectMode")).unwrap(),
+      "inactive-or-failed"
+    );
+  }
+}
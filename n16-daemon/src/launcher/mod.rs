@@ -1,89 +1,238 @@
 use iced::{Element, Subscription, Task, window};
 use iced_layershell::{
   reexport::{Anchor, NewLayerShellSettings},
-  settings::{LayerShellSettings, StartMode},
+  settings::{LayerShellSettings, Settings, StartMode},
   to_layer_message,
 };
-use n16_core::theme::Base16Theme;
-use n16_ipc::{Response, launcher::Request};
+use n16_core::{
+  config::{self, LauncherConfig, StyleConfig, ThemeConfig},
+  theme::{Base16Theme, ColorScheme},
+};
+use n16_ipc::{
+  Response,
+  launcher::{self, DmenuRequest, DmenuSelection, Request},
+};
 use std::collections::HashMap;
+use wayland_client::Connection;
 
 use crate::{
-  application::{ApplicationRequest, N16Application, RequestChannel},
+  appearance,
+  application::{ApplicationRequest, N16Application, RequestChannel, default_font},
   launcher::{
     gui::Launcher,
     providers::{
-      ApplicationProvider, CalculatorProvider, PowerManagementProvider, Providers, ProvidersBuilder,
+      ApplicationProvider, CalculatorProvider, DmenuProvider, PowerManagementProvider, ProviderId,
+      Providers, ProvidersBuilder,
     },
   },
 };
 
 mod component;
 mod gui;
+mod launch;
 pub mod providers;
 
 #[to_layer_message(multi)]
 #[derive(Debug, Clone)]
 enum Message {
   RequestRecieved(ApplicationRequest<Request>),
+  ColorSchemeChanged(ColorScheme),
   Launcher(window::Id, gui::Message),
   Close(window::Id),
 }
 
 pub struct LauncherDaemon {
+  config: LauncherConfig,
+  themes: ThemeConfig,
+  color_scheme: ColorScheme,
+  style: StyleConfig,
   providers: Providers,
   launcher_windows: HashMap<window::Id, Launcher>,
+  /// Senders for the selection of launcher windows opened by dmenu requests
+  dmenu_windows: HashMap<window::Id, async_channel::Sender<DmenuSelection>>,
 }
 
 impl LauncherDaemon {
   pub fn new() -> Self {
+    let config = config::load_config_file().unwrap_or_default();
+    appearance::set_themes(&config);
+
     Self {
-      providers: Self::setup_providers(),
+      config: config.launcher().clone(),
+      themes: config.themes().clone(),
+      color_scheme: appearance::color_scheme(),
+      style: config.style().clone(),
+      providers: Self::setup_providers(config.launcher()),
       launcher_windows: HashMap::new(),
+      dmenu_windows: HashMap::new(),
     }
   }
 
-  pub fn setup_providers() -> Providers {
+  /// Reload the config file and reinitialize the providers, and the themes served over IPC.
+  /// Open launcher windows keep using the previous providers until they are closed.
+  fn reload(&mut self) {
+    let config = config::load_config_file().unwrap_or_default();
+    appearance::set_themes(&config);
+
+    self.providers = Self::setup_providers(config.launcher());
+    self.config = config.launcher().clone();
+    self.themes = config.themes().clone();
+    self.style = config.style().clone();
+  }
+
+  pub fn setup_providers(config: &LauncherConfig) -> Providers {
     let mut builder = ProvidersBuilder::new();
 
-    builder.add_provider::<CalculatorProvider>();
-    builder.add_provider::<ApplicationProvider>();
-    builder.add_provider::<PowerManagementProvider>();
+    builder.add_provider::<CalculatorProvider>(config.calculator());
+    builder.add_provider::<ApplicationProvider>(config.applications());
+    builder.add_provider::<PowerManagementProvider>(config.power_management());
 
     builder.build()
   }
 
-  fn open_launcher(&mut self) -> Task<Message> {
+  fn open_launcher(
+    &mut self,
+    providers: Providers,
+    options: gui::Options,
+  ) -> Option<(window::Id, Task<Message>)> {
     if !self.launcher_windows.is_empty() {
-      return Task::none();
+      return None;
     }
 
+    let launcher_style = self.style.launcher();
     let (id, window_task) = Message::layershell_open(NewLayerShellSettings {
-      size: Some((1000, 600)),
+      size: Some((launcher_style.width(), launcher_style.height())),
       anchor: Anchor::Top,
-      margin: Some((200, 0, 0, 0)),
+      margin: Some((launcher_style.margin_top(), 0, 0, 0)),
       ..Default::default()
     });
 
-    let (launcher_window, launcher_task) = Launcher::new(self.providers.clone());
+    let (launcher_window, launcher_task) =
+      Launcher::new(providers, self.config.clone(), self.style.clone(), options);
     self.launcher_windows.insert(id, launcher_window);
 
-    Task::batch([
-      window_task,
-      launcher_task.map(move |m| Message::Launcher(id, m)),
-    ])
+    Some((
+      id,
+      Task::batch([
+        window_task,
+        launcher_task.map(move |m| Message::Launcher(id, m)),
+      ]),
+    ))
+  }
+
+  fn open_request(
+    &mut self,
+    request: ApplicationRequest<Request>,
+    providers: Option<Vec<ProviderId>>,
+    query: Option<String>,
+    placeholder: Option<String>,
+  ) -> Task<Message> {
+    let providers = match providers {
+      Some(ids) => match self.providers.filtered(&ids) {
+        Ok(providers) => providers,
+        Err(id) => {
+          request.reply(Err(format!("Unknown launcher provider `{id}`")));
+          return Task::none();
+        }
+      },
+      None => self.providers.clone(),
+    };
+
+    request.reply(Response::Handled);
+
+    let options = gui::Options {
+      query: query.unwrap_or_default(),
+      placeholder,
+      ..Default::default()
+    };
+
+    self
+      .open_launcher(providers, options)
+      .map_or_else(Task::none, |(_, task)| task)
+  }
+
+  fn close_all(&self) -> Task<Message> {
+    Task::batch(
+      self
+        .launcher_windows
+        .keys()
+        .map(|id| Task::done(Message::Close(*id))),
+    )
+  }
+
+  fn open_dmenu(
+    &mut self,
+    request: ApplicationRequest<Request>,
+    dmenu: DmenuRequest,
+  ) -> Task<Message> {
+    let (selection_tx, selection_rx) = async_channel::bounded(1);
+
+    let mut builder = ProvidersBuilder::new();
+    builder.add_provider_instance(
+      DmenuProvider::info(),
+      DmenuProvider::new(dmenu.items, selection_tx.clone()),
+    );
+
+    let options = gui::Options {
+      placeholder: dmenu.prompt,
+      secure: dmenu.password,
+      submit_query: true,
+      fuzzy: true,
+      ..Default::default()
+    };
+
+    let Some((id, task)) = self.open_launcher(builder.build(), options) else {
+      request.reply(Err("The launcher is already open".to_string()));
+      return Task::none();
+    };
+
+    self.dmenu_windows.insert(id, selection_tx);
+
+    // The channel closes without a selection once the window and its providers are dropped
+    let reply_task = Task::future(async move {
+      let selection = selection_rx.recv().await.ok();
+      request.reply(Response::Launcher(launcher::Response::DmenuSelection(
+        selection,
+      )));
+    })
+    .discard();
+
+    Task::batch([task, reply_task])
   }
 
   fn handle_request(&mut self, request: ApplicationRequest<Request>) -> Task<Message> {
     match request.kind() {
-      Request::Open => {
+      Request::Toggle { .. } if !self.launcher_windows.is_empty() => {
         request.reply(Response::Handled);
-        self.open_launcher()
+        self.close_all()
+      }
+      Request::Open {
+        providers,
+        query,
+        placeholder,
+      }
+      | Request::Toggle {
+        providers,
+        query,
+        placeholder,
+      } => {
+        let (providers, query, placeholder) =
+          (providers.clone(), query.clone(), placeholder.clone());
+        self.open_request(request, providers, query, placeholder)
       }
       Request::Close => {
         request.reply(Response::Handled);
         window::latest().and_then(|id| Task::done(Message::Close(id)))
       }
+      Request::Dmenu(dmenu) => {
+        let dmenu = dmenu.clone();
+        self.open_dmenu(request, dmenu)
+      }
+      Request::Reload => {
+        self.reload();
+        request.reply(Response::Handled);
+        Task::none()
+      }
     }
   }
 }
@@ -92,11 +241,25 @@ impl LauncherDaemon {
   fn update(&mut self, message: Message) -> Task<Message> {
     match message {
       Message::RequestRecieved(request) => self.handle_request(request),
+      Message::ColorSchemeChanged(scheme) => {
+        self.color_scheme = scheme;
+        Task::none()
+      }
       Message::Launcher(id, message) => {
         if let Some(launcher) = self.launcher_windows.get_mut(&id) {
           match launcher.update(message) {
             gui::Action::Task(task) => task.map(move |m| Message::Launcher(id, m)),
             gui::Action::Close => Task::done(Message::Close(id)),
+            gui::Action::SubmitQuery(query) => {
+              if let Some(selection_tx) = self.dmenu_windows.get(&id) {
+                let _ = selection_tx.try_send(DmenuSelection {
+                  index: None,
+                  text: query,
+                });
+              }
+
+              Task::done(Message::Close(id))
+            }
           }
         } else {
           Task::none()
@@ -106,6 +269,7 @@ impl LauncherDaemon {
       Message::Close(id) => {
         if self.launcher_windows.contains_key(&id) {
           self.launcher_windows.remove(&id);
+          self.dmenu_windows.remove(&id);
           window::close(id)
         } else {
           Task::none()
@@ -126,13 +290,23 @@ impl LauncherDaemon {
     }
   }
 
+  fn theme(&self, _window_id: window::Id) -> Base16Theme {
+    self
+      .themes
+      .theme(self.color_scheme, self.style.theme_style())
+  }
+
   fn subscription(&self) -> Subscription<Message> {
-    Subscription::batch(self.launcher_windows.iter().map(|(id, launcher)| {
+    let launchers = self.launcher_windows.iter().map(|(id, launcher)| {
       launcher
         .subscription(*id)
         .with(*id)
         .map(|(id, m)| Message::Launcher(id, m))
-    }))
+    });
+
+    Subscription::batch(launchers.chain([
+      Subscription::run(appearance::color_scheme_changes).map(Message::ColorSchemeChanged),
+    ]))
   }
 }
 
@@ -140,6 +314,21 @@ impl N16Application for LauncherDaemon {
   type Request = Request;
 
   fn run(request_rx: RequestChannel<Self::Request>) {
+    let style = config::load_config_file()
+      .unwrap_or_default()
+      .style()
+      .clone();
+
+    // Shared with `iced_layershell`, so activation tokens can be tied to the launcher's surface
+    let connection = match Connection::connect_to_env() {
+      Ok(connection) => connection,
+      Err(error) => {
+        eprintln!("Failed to connect to the Wayland compositor: {error}");
+        return;
+      }
+    };
+    launch::set_connection(connection.clone());
+
     let _ = iced_layershell::daemon(
       move || {
         (
@@ -151,11 +340,18 @@ impl N16Application for LauncherDaemon {
       LauncherDaemon::update,
       LauncherDaemon::view,
     )
+    .settings(Settings {
+      with_connection: Some(connection.into()),
+      ..Default::default()
+    })
     .layer_settings(LayerShellSettings {
       start_mode: StartMode::Background,
       ..Default::default()
     })
     .subscription(LauncherDaemon::subscription)
+    .theme(LauncherDaemon::theme)
+    .default_font(default_font(&style))
+    .default_text_size(style.font_size())
     .run();
   }
 }
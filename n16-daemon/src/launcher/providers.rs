@@ -1,25 +1,21 @@
 mod applications;
 mod calculator;
+mod dmenu;
 mod power_management;
 
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, fmt, sync::Arc, time::Duration};
 
-pub use applications::ApplicationProvider;
+pub use applications::{ApplicationProvider, IconIndex};
 use async_trait::async_trait;
 pub use calculator::CalculatorProvider;
+pub use dmenu::DmenuProvider;
 use futures_lite::Stream;
 use iced::widget::{image, svg};
+use n16_core::config::ProviderSettings;
 pub use power_management::PowerManagementProvider;
+use tokio_util::sync::CancellationToken;
 
-#[derive(Debug, Clone)]
-pub enum ProviderType {
-  /// Provides a static list of matches to be filtered by the search text
-  Static,
-  /// Provides a dynamic list of matches based on the search text
-  Dynamic,
-}
-
-pub type ProviderId = String;
+pub use n16_ipc::launcher::ProviderId;
 
 #[derive(Debug, Clone)]
 pub struct ProviderInfo {
@@ -29,32 +25,302 @@ pub struct ProviderInfo {
   pub name: String,
   /// Priority, higher appears first
   pub priorty: i64,
-  /// Provider type
-  pub provider_type: ProviderType,
+  /// Maximum number of matches shown, or all matches if `None`
+  pub max_results: Option<usize>,
 }
 
 #[derive(Debug, Clone)]
-pub enum ExecutionFinishAction {
+pub enum ExecutionFinishAction<D = AnyMatchData> {
   Close,
+  /// Keep the launcher open without changing it
+  KeepOpen,
+  /// Replace the search text, keeping the launcher open
+  SetQuery(String),
+  /// Ask to run the match again to confirm `action`, such as `Shut Down`, in place of the executed
+  /// match. [`Provider::execute_confirmed`] is run if the match is run again within `timeout`.
+  RequestConfirmation {
+    action: String,
+    timeout: Duration,
+  },
+  /// Replace the provider's matches for the rest of the launcher session.
+  /// The new matches are filtered by the search text like static matches.
+  ReplaceMatches(Vec<Match<D>>),
+  /// Show a message above the matches until the search text changes
+  ShowMessage {
+    title: String,
+    body: String,
+    is_error: bool,
+  },
+  /// Only show the matches of a provider with an empty search text.
+  /// The `close` keybind goes back to the previous view.
+  PushProviderView(ProviderId),
+}
+
+impl<D> ExecutionFinishAction<D> {
+  fn erase_data<P: Provider<Data = D>>(self, provider: &Arc<P>) -> ExecutionFinishAction {
+    match self {
+      Self::Close => ExecutionFinishAction::Close,
+      Self::KeepOpen => ExecutionFinishAction::KeepOpen,
+      Self::SetQuery(query) => ExecutionFinishAction::SetQuery(query),
+      Self::RequestConfirmation { action, timeout } => {
+        ExecutionFinishAction::RequestConfirmation { action, timeout }
+      }
+      Self::ReplaceMatches(matches) => ExecutionFinishAction::ReplaceMatches(
+        matches
+          .into_iter()
+          .map(|m| m.erase_data(provider))
+          .collect(),
+      ),
+      Self::ShowMessage {
+        title,
+        body,
+        is_error,
+      } => ExecutionFinishAction::ShowMessage {
+        title,
+        body,
+        is_error,
+      },
+      Self::PushProviderView(id) => ExecutionFinishAction::PushProviderView(id),
+    }
+  }
+}
+
+/// How a match is executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionMode {
+  /// Triggered by the `run-selected` keybind or by clicking the match
+  Default,
+  /// Triggered by the `run-selected-alt` keybind
+  Alt,
+  /// Running the match again after it requested confirmation
+  Confirmed,
 }
 
+/// Source of launcher matches.
+///
+/// A provider supplies static matches that are filtered by the search text by implementing
+/// [`StaticProvider`], dynamic matches computed from the search text by implementing
+/// [`DynamicProvider`], or both. [`Provider::Kind`] declares which of them it implements.
 #[async_trait]
-pub trait Provider: Send {
+pub trait Provider: Send + Sync + 'static {
+  /// Data attached to each match, passed back when the match is executed
+  type Data: Clone + Send + Sync + 'static;
+
+  /// Config of the provider, read from its `provider` node in the launcher config
+  type Config: AsRef<ProviderSettings>;
+
+  /// [`Static`], [`Dynamic`] or [`StaticAndDynamic`]
+  type Kind: ProviderKind<Self>;
+
   /// Initialize the provider and return it's information.
-  /// Called on startup and when config is refreshed
-  fn init() -> (ProviderInfo, Self)
+  /// Called on startup and when config is refreshed.
+  ///
+  /// The name and priority in the returned information are defaults, overridden by the config.
+  fn init(config: Self::Config) -> (ProviderInfo, Self)
   where
     Self: Sized;
 
-  /// Get the static matches
-  /// Only called on `ProviderType::Static` Providers
+  /// Called when a launcher window using this provider is opened
+  fn session_started(&self) {}
+
+  async fn execute_match(
+    &self,
+    selected_match: Match<Self::Data>,
+  ) -> ExecutionFinishAction<Self::Data>;
+
+  /// Alternative execution of a match, triggered by the `run-selected-alt` keybind.
+  /// Defaults to [`Provider::execute_match`]
+  async fn execute_match_alt(
+    &self,
+    selected_match: Match<Self::Data>,
+  ) -> ExecutionFinishAction<Self::Data> {
+    self.execute_match(selected_match).await
+  }
+
+  /// Execution of a match that returned [`ExecutionFinishAction::RequestConfirmation`]
+  /// and was run again to confirm it. Defaults to [`Provider::execute_match`]
+  async fn execute_confirmed(
+    &self,
+    selected_match: Match<Self::Data>,
+  ) -> ExecutionFinishAction<Self::Data> {
+    self.execute_match(selected_match).await
+  }
+}
+
+/// A provider of static matches
+#[async_trait]
+pub trait StaticProvider: Provider {
+  /// Get the static matches, filtered by the launcher using the search text
+  async fn matches(&self) -> Vec<Match<Self::Data>>;
+}
+
+/// A provider of dynamic matches
+#[async_trait]
+pub trait DynamicProvider: Provider {
+  /// Get the dynamic matches for the search text, shown after the static matches.
+  /// `cancel` is cancelled once the search text changes again.
+  async fn matches_dynamic(
+    &self,
+    search_text: String,
+    cancel: CancellationToken,
+  ) -> Vec<Match<Self::Data>>;
+}
+
+/// Which matches a provider supplies, see [`Provider::Kind`]
+#[async_trait]
+pub trait ProviderKind<P: Provider + ?Sized> {
+  /// Whether the provider implements [`DynamicProvider`]
+  const DYNAMIC: bool;
+
+  async fn matches(provider: &P) -> Vec<Match<P::Data>>;
+
+  async fn matches_dynamic(
+    provider: &P,
+    search_text: String,
+    cancel: CancellationToken,
+  ) -> Vec<Match<P::Data>>;
+}
+
+/// Kind of a [`StaticProvider`]
+pub struct Static;
+
+/// Kind of a [`DynamicProvider`]
+pub struct Dynamic;
+
+/// Kind of a provider implementing both [`StaticProvider`] and [`DynamicProvider`], such as static
+/// defaults with extra matches for the search text
+#[cfg_attr(
+  not(test),
+  expect(
+    dead_code,
+    reason = "No built-in provider supplies both kinds of matches"
+  )
+)]
+pub struct StaticAndDynamic;
+
+#[async_trait]
+impl<P: StaticProvider + ?Sized> ProviderKind<P> for Static {
+  const DYNAMIC: bool = false;
+
+  async fn matches(provider: &P) -> Vec<Match<P::Data>> {
+    provider.matches().await
+  }
+
+  async fn matches_dynamic(_: &P, _: String, _: CancellationToken) -> Vec<Match<P::Data>> {
+    Vec::new()
+  }
+}
+
+#[async_trait]
+impl<P: DynamicProvider + ?Sized> ProviderKind<P> for Dynamic {
+  const DYNAMIC: bool = true;
+
+  async fn matches(_: &P) -> Vec<Match<P::Data>> {
+    Vec::new()
+  }
+
+  async fn matches_dynamic(
+    provider: &P,
+    search_text: String,
+    cancel: CancellationToken,
+  ) -> Vec<Match<P::Data>> {
+    provider.matches_dynamic(search_text, cancel).await
+  }
+}
+
+#[async_trait]
+impl<P: StaticProvider + DynamicProvider + ?Sized> ProviderKind<P> for StaticAndDynamic {
+  const DYNAMIC: bool = true;
+
+  async fn matches(provider: &P) -> Vec<Match<P::Data>> {
+    provider.matches().await
+  }
+
+  async fn matches_dynamic(
+    provider: &P,
+    search_text: String,
+    cancel: CancellationToken,
+  ) -> Vec<Match<P::Data>> {
+    provider.matches_dynamic(search_text, cancel).await
+  }
+}
+
+/// Match data with the provider's data type erased
+pub type AnyMatchData = Arc<dyn MatchData>;
+
+/// Data of a match bound to the provider that created it, so the match is executed by that
+/// provider with its own data type
+#[async_trait]
+pub trait MatchData: Send + Sync {
+  async fn execute(&self, selected_match: Match<()>, mode: ExecutionMode) -> ExecutionFinishAction;
+}
+
+impl fmt::Debug for dyn MatchData {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("MatchData").finish_non_exhaustive()
+  }
+}
+
+struct BoundData<P: Provider> {
+  provider: Arc<P>,
+  data: P::Data,
+}
+
+#[async_trait]
+impl<P: Provider> MatchData for BoundData<P> {
+  async fn execute(&self, selected_match: Match<()>, mode: ExecutionMode) -> ExecutionFinishAction {
+    let selected_match = selected_match.map_data(|()| self.data.clone());
+    let provider = &*self.provider;
+
+    let action = match mode {
+      ExecutionMode::Default => provider.execute_match(selected_match).await,
+      ExecutionMode::Alt => provider.execute_match_alt(selected_match).await,
+      ExecutionMode::Confirmed => provider.execute_confirmed(selected_match).await,
+    };
+
+    action.erase_data(&self.provider)
+  }
+}
+
+/// Object safe version of [`Provider`] used to store providers with different data types
+#[async_trait]
+trait AnyProvider: Send + Sync {
+  fn session_started(&self);
+
+  /// Whether the provider supplies dynamic matches
+  fn is_dynamic(&self) -> bool;
+
   async fn matches(&self) -> Vec<Match>;
 
-  /// Update the dynamic matches based on the search text
-  /// Only called on `ProviderType::Dynamic` Providers
-  async fn matches_dynamic(&self, search_text: String) -> Vec<Match>;
+  async fn matches_dynamic(&self, search_text: String, cancel: CancellationToken) -> Vec<Match>;
+}
+
+// Implemented for the `Arc` so the matches can keep their provider, see [`BoundData`]
+#[async_trait]
+impl<P: Provider> AnyProvider for Arc<P> {
+  fn session_started(&self) {
+    Provider::session_started(&**self);
+  }
+
+  fn is_dynamic(&self) -> bool {
+    P::Kind::DYNAMIC
+  }
 
-  async fn execute_match(&self, selected_match: Match) -> ExecutionFinishAction;
+  async fn matches(&self) -> Vec<Match> {
+    P::Kind::matches(self)
+      .await
+      .into_iter()
+      .map(|m| m.erase_data(self))
+      .collect()
+  }
+
+  async fn matches_dynamic(&self, search_text: String, cancel: CancellationToken) -> Vec<Match> {
+    P::Kind::matches_dynamic(self, search_text, cancel)
+      .await
+      .into_iter()
+      .map(|m| m.erase_data(self))
+      .collect()
+  }
 }
 
 #[derive(Debug, Clone)]
@@ -64,7 +330,7 @@ pub enum MatchIcon {
 }
 
 #[derive(Debug, Clone)]
-pub struct Match {
+pub struct Match<D = AnyMatchData> {
   /// Title shown to the user
   pub title: String,
   /// Optional description shown to the user
@@ -76,8 +342,48 @@ pub struct Match {
 
   /// Whether the match can be executed
   pub executable: bool,
-  /// Id used for idenitfying matches when handling their execution
-  pub id: u64,
+  /// Data used for identifying matches when handling their execution
+  pub data: D,
+}
+
+impl<D> Match<D> {
+  fn map_data<E>(self, f: impl FnOnce(D) -> E) -> Match<E> {
+    Match {
+      title: self.title,
+      description: self.description,
+      icon: self.icon,
+      keywords: self.keywords,
+      executable: self.executable,
+      data: f(self.data),
+    }
+  }
+
+  fn erase_data<P: Provider<Data = D>>(self, provider: &Arc<P>) -> Match {
+    self.map_data(|data| -> AnyMatchData {
+      Arc::new(BoundData {
+        provider: Arc::clone(provider),
+        data,
+      })
+    })
+  }
+}
+
+impl Match {
+  /// Execute the match with the provider that created it
+  pub fn execute(
+    self,
+    mode: ExecutionMode,
+  ) -> impl Future<Output = Option<ExecutionFinishAction>> + use<> {
+    let (action_tx, action_rx) = async_channel::unbounded();
+    tokio::spawn(async move {
+      let data = Arc::clone(&self.data);
+      let action = data.execute(self.map_data(|_| ()), mode).await;
+
+      let _ = action_tx.send(action).await;
+    });
+
+    async move { action_rx.recv().await.ok() }
+  }
 }
 
 #[derive(Debug, Clone)]
@@ -86,7 +392,7 @@ pub struct Matches {
   pub matches: Vec<Match>,
 }
 
-type ProvidersInner = HashMap<ProviderId, (ProviderInfo, Box<dyn Provider + Sync>)>;
+type ProvidersInner = HashMap<ProviderId, (ProviderInfo, Arc<dyn AnyProvider>)>;
 
 #[derive(Clone)]
 pub struct Providers {
@@ -94,6 +400,22 @@ pub struct Providers {
 }
 
 impl Providers {
+  /// Create a set of providers containing only the providers in `ids`.
+  /// Returns the first unknown id if any of the providers don't exist.
+  pub fn filtered(&self, ids: &[ProviderId]) -> Result<Providers, ProviderId> {
+    let providers = ids
+      .iter()
+      .map(|id| match self.providers.get(id) {
+        Some((info, provider)) => Ok((id.clone(), (info.clone(), Arc::clone(provider)))),
+        None => Err(id.clone()),
+      })
+      .collect::<Result<_, _>>()?;
+
+    Ok(Providers {
+      providers: Arc::new(providers),
+    })
+  }
+
   pub fn get_sorted_provider_info(&self) -> Vec<ProviderInfo> {
     let mut info: Vec<ProviderInfo> = self
       .providers
@@ -106,60 +428,76 @@ impl Providers {
     info
   }
 
+  /// Notify the providers that a launcher window using them was opened
+  pub fn start_session(&self) {
+    for (_, provider) in self.providers.values() {
+      provider.session_started();
+    }
+  }
+
   pub fn get_static_matches(&mut self) -> impl Stream<Item = Matches> + use<> {
     let (matches_tx, matches_rx) = async_channel::unbounded();
     let providers = Arc::clone(&self.providers);
     tokio::spawn(async move {
       for (info, provider) in providers.values() {
-        if matches!(info.provider_type, ProviderType::Static) {
-          let _ = matches_tx.try_send(Matches {
-            id: info.id.clone(),
-            matches: provider.matches().await,
-          });
-        }
+        let _ = matches_tx.try_send(Matches {
+          id: info.id.clone(),
+          matches: provider.matches().await,
+        });
       }
     });
 
     matches_rx
   }
 
-  pub fn get_dynamic_matches(&mut self, query: String) -> impl Stream<Item = Matches> + use<> {
+  /// Query every dynamic provider concurrently once `debounce` has passed without `cancel` being
+  /// cancelled. The matches of each provider are streamed as soon as it finishes, providers taking
+  /// longer than `timeout` are skipped.
+  pub fn get_dynamic_matches(
+    &mut self,
+    query: String,
+    cancel: CancellationToken,
+    debounce: Duration,
+    timeout: Duration,
+  ) -> impl Stream<Item = Matches> + use<> {
     let (matches_tx, matches_rx) = async_channel::unbounded();
     let providers = Arc::clone(&self.providers);
     tokio::spawn(async move {
-      for (info, provider) in providers.values() {
-        if matches!(info.provider_type, ProviderType::Dynamic) {
-          let _ = matches_tx.try_send(Matches {
-            id: info.id.clone(),
-            matches: provider.matches_dynamic(query.clone()).await,
-          });
-        }
+      if cancel
+        .run_until_cancelled(tokio::time::sleep(debounce))
+        .await
+        .is_none()
+      {
+        return;
       }
-    });
 
-    matches_rx
-  }
+      let dynamic = providers
+        .values()
+        .filter(|(_, provider)| provider.is_dynamic());
 
-  pub fn execute_match(
-    &self,
-    (id, selected_match): (String, Match),
-  ) -> impl Future<Output = Option<ExecutionFinishAction>> + use<> {
-    let (action_tx, action_rx) = async_channel::unbounded();
-    let providers = Arc::clone(&self.providers);
-    tokio::spawn(async move {
-      let Some((_, provider)) = providers.get(&id) else {
-        let _ = action_tx.send(None).await;
-        return;
-      };
+      for (info, provider) in dynamic {
+        let id = info.id.clone();
+        let provider = Arc::clone(provider);
+        let query = query.clone();
+        let cancel = cancel.clone();
+        let matches_tx = matches_tx.clone();
 
-      println!("Processing provider: {id}");
+        tokio::spawn(async move {
+          let matches =
+            tokio::time::timeout(timeout, provider.matches_dynamic(query, cancel.clone()));
 
-      let _ = action_tx
-        .send(Some(provider.execute_match(selected_match).await))
-        .await;
+          match cancel.run_until_cancelled(matches).await {
+            Some(Ok(matches)) => {
+              let _ = matches_tx.try_send(Matches { id, matches });
+            }
+            Some(Err(_)) => eprintln!("Provider {id} timed out"),
+            None => {}
+          }
+        });
+      }
     });
 
-    async move { action_rx.recv().await.ok().flatten() }
+    matches_rx
   }
 }
 
@@ -174,11 +512,34 @@ impl ProvidersBuilder {
     }
   }
 
-  pub fn add_provider<P: Provider + Sync + 'static>(&mut self) {
-    let (info, provider) = P::init();
+  /// Initialize and add a provider, unless it is disabled by its config
+  pub fn add_provider<P: Provider>(&mut self, config: P::Config) {
+    let settings = config.as_ref().clone();
+
+    if !settings.enabled() {
+      return;
+    }
+
+    let (mut info, provider) = P::init(config);
+
+    if let Some(name) = settings.name() {
+      info.name = name.to_string();
+    }
+
+    if let Some(priority) = settings.priority() {
+      info.priorty = priority;
+    }
+
+    info.max_results = settings.max_results();
+
+    self.add_provider_instance(info, provider);
+  }
+
+  /// Add a provider that was created without [`Provider::init`]
+  pub fn add_provider_instance<P: Provider>(&mut self, info: ProviderInfo, provider: P) {
     self
       .providers
-      .insert(info.id.clone(), (info, Box::new(provider)));
+      .insert(info.id.clone(), (info, Arc::new(Arc::new(provider))));
   }
 
   pub fn build(self) -> Providers {
@@ -187,3 +548,197 @@ impl ProvidersBuilder {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use futures_lite::StreamExt;
+
+  use super::*;
+
+  /// Static matches titled by their data, executing a match replaces the query with its data
+  struct WordsProvider(Vec<&'static str>);
+
+  #[async_trait]
+  impl Provider for WordsProvider {
+    type Data = &'static str;
+    type Config = ProviderSettings;
+    type Kind = Static;
+
+    fn init(_config: ProviderSettings) -> (ProviderInfo, Self) {
+      (info("words"), Self(Vec::new()))
+    }
+
+    async fn execute_match(
+      &self,
+      selected_match: Match<&'static str>,
+    ) -> ExecutionFinishAction<&'static str> {
+      ExecutionFinishAction::SetQuery(selected_match.data.into())
+    }
+  }
+
+  #[async_trait]
+  impl StaticProvider for WordsProvider {
+    async fn matches(&self) -> Vec<Match<&'static str>> {
+      self
+        .0
+        .iter()
+        .map(|word| Match {
+          title: (*word).into(),
+          description: None,
+          icon: None,
+          keywords: Vec::new(),
+          executable: true,
+          data: *word,
+        })
+        .collect()
+    }
+  }
+
+  /// A single dynamic match titled by the search text
+  struct EchoProvider;
+
+  #[async_trait]
+  impl Provider for EchoProvider {
+    type Data = ();
+    type Config = ProviderSettings;
+    type Kind = Dynamic;
+
+    fn init(_config: ProviderSettings) -> (ProviderInfo, Self) {
+      (info("echo"), Self)
+    }
+
+    async fn execute_match(&self, _: Match<()>) -> ExecutionFinishAction<()> {
+      ExecutionFinishAction::Close
+    }
+  }
+
+  #[async_trait]
+  impl DynamicProvider for EchoProvider {
+    async fn matches_dynamic(&self, search_text: String, _: CancellationToken) -> Vec<Match<()>> {
+      vec![unit_match(&search_text)]
+    }
+  }
+
+  /// A static default match, followed by a dynamic match titled by the search text
+  struct DefaultsProvider;
+
+  #[async_trait]
+  impl Provider for DefaultsProvider {
+    type Data = ();
+    type Config = ProviderSettings;
+    type Kind = StaticAndDynamic;
+
+    fn init(_config: ProviderSettings) -> (ProviderInfo, Self) {
+      (info("defaults"), Self)
+    }
+
+    async fn execute_match(&self, _: Match<()>) -> ExecutionFinishAction<()> {
+      ExecutionFinishAction::Close
+    }
+  }
+
+  #[async_trait]
+  impl StaticProvider for DefaultsProvider {
+    async fn matches(&self) -> Vec<Match<()>> {
+      vec![unit_match("default")]
+    }
+  }
+
+  #[async_trait]
+  impl DynamicProvider for DefaultsProvider {
+    async fn matches_dynamic(&self, search_text: String, _: CancellationToken) -> Vec<Match<()>> {
+      vec![unit_match(&search_text)]
+    }
+  }
+
+  fn unit_match(title: &str) -> Match<()> {
+    Match {
+      title: title.into(),
+      description: None,
+      icon: None,
+      keywords: Vec::new(),
+      executable: true,
+      data: (),
+    }
+  }
+
+  fn info(id: &str) -> ProviderInfo {
+    ProviderInfo {
+      id: id.into(),
+      name: id.into(),
+      priorty: 0,
+      max_results: None,
+    }
+  }
+
+  #[tokio::test]
+  async fn matches_execute_with_their_provider_data() {
+    let mut builder = ProvidersBuilder::new();
+    builder.add_provider_instance(info("first"), WordsProvider(vec!["one", "two"]));
+    builder.add_provider_instance(info("second"), WordsProvider(vec!["three"]));
+    let mut providers = builder.build();
+
+    let matches: Vec<Matches> = providers.get_static_matches().collect().await;
+    assert_eq!(matches.len(), 2);
+
+    for Matches { matches, .. } in matches {
+      for m in matches {
+        let title = m.title.clone();
+        let action = m.execute(ExecutionMode::Default).await;
+
+        assert!(matches!(action, Some(ExecutionFinishAction::SetQuery(query)) if query == title));
+      }
+    }
+  }
+
+  #[tokio::test]
+  async fn dynamic_matches_are_only_requested_from_dynamic_providers() {
+    let mut builder = ProvidersBuilder::new();
+    builder.add_provider_instance(info("words"), WordsProvider(vec!["one"]));
+    builder.add_provider_instance(info("echo"), EchoProvider);
+    let mut providers = builder.build();
+
+    let matches: Vec<Matches> = providers
+      .get_dynamic_matches(
+        "query".into(),
+        CancellationToken::new(),
+        Duration::ZERO,
+        Duration::from_secs(1),
+      )
+      .collect()
+      .await;
+
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].id, "echo");
+    assert_eq!(matches[0].matches[0].title, "query");
+  }
+
+  #[tokio::test]
+  async fn combined_provider_supplies_both_kinds_of_matches() {
+    let mut builder = ProvidersBuilder::new();
+    builder.add_provider_instance(info("defaults"), DefaultsProvider);
+    let mut providers = builder.build();
+
+    let titles = |matches: Vec<Matches>| -> Vec<String> {
+      matches
+        .into_iter()
+        .flat_map(|m| m.matches)
+        .map(|m| m.title)
+        .collect()
+    };
+
+    let static_matches = providers.get_static_matches().collect().await;
+    assert_eq!(titles(static_matches), ["default"]);
+
+    let dynamic_matches = providers
+      .get_dynamic_matches(
+        "query".into(),
+        CancellationToken::new(),
+        Duration::ZERO,
+        Duration::from_secs(1),
+      )
+      .collect()
+      .await;
+    assert_eq!(titles(dynamic_matches), ["query"]);
+  }
+}
@@ -0,0 +1,315 @@
+//! Index of the icon files contained in each icon directory.
+//!
+//! Scanning every size directory of every theme for each desktop entry is slow on systems with large
+//! icon themes, so each directory is read once and its icons are indexed by name.
+//! The index is persisted under `$XDG_CACHE_HOME/n16-shell` and a directory is only rescanned
+//! when its modification time changes.
+
+use std::{
+  collections::HashMap,
+  fs, io,
+  path::{Path, PathBuf},
+  time::UNIX_EPOCH,
+};
+
+use serde::{Deserialize, Serialize};
+use xdg::BaseDirectories;
+
+const CACHE_FILE: &str = "icon-index.json";
+
+/// Bumped whenever the layout of the cache file changes, invalidating old caches
+const CACHE_VERSION: u32 = 1;
+
+/// Icon file extensions in order of preference
+const EXTENSIONS: [&str; 3] = ["png", "svg", "xpm"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct Mtime {
+  secs: u64,
+  nanos: u32,
+}
+
+impl Mtime {
+  fn of(dir: &Path) -> io::Result<Self> {
+    let since_epoch = fs::metadata(dir)?
+      .modified()?
+      .duration_since(UNIX_EPOCH)
+      .unwrap_or_default();
+
+    Ok(Self {
+      secs: since_epoch.as_secs(),
+      nanos: since_epoch.subsec_nanos(),
+    })
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DirIndex {
+  /// Modification time of the directory when it was indexed
+  mtime: Mtime,
+  /// File names of the icons in this directory, keyed by icon name
+  icons: HashMap<String, Vec<String>>,
+}
+
+impl DirIndex {
+  fn scan(dir: &Path, mtime: Mtime) -> io::Result<Self> {
+    let mut icons: HashMap<String, Vec<String>> = HashMap::new();
+
+    for entry in fs::read_dir(dir)? {
+      let Ok(entry) = entry else {
+        continue;
+      };
+
+      let file_name = entry.file_name();
+      let Some(file_name) = file_name.to_str() else {
+        continue;
+      };
+
+      // Skip hidden files and files without an extension
+      let Some((icon_name, _)) = file_name.rsplit_once('.') else {
+        continue;
+      };
+
+      if icon_name.is_empty() {
+        continue;
+      }
+
+      icons
+        .entry(icon_name.to_string())
+        .or_default()
+        .push(file_name.to_string());
+    }
+
+    for candidates in icons.values_mut() {
+      candidates.sort_by_key(|file_name| extension_rank(file_name));
+    }
+
+    Ok(Self { mtime, icons })
+  }
+}
+
+fn extension_rank(file_name: &str) -> usize {
+  let extension = file_name.rsplit_once('.').map_or("", |(_, ext)| ext);
+
+  EXTENSIONS
+    .iter()
+    .position(|preferred| *preferred == extension)
+    .unwrap_or(EXTENSIONS.len())
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+  version: u32,
+  directories: HashMap<PathBuf, DirIndex>,
+}
+
+#[derive(Debug, Default)]
+pub struct IconIndex {
+  directories: HashMap<PathBuf, DirIndex>,
+}
+
+impl IconIndex {
+  /// Index every directory in `dirs`, reusing the cached index for directories that have not
+  /// been modified since they were last scanned.
+  pub fn build<'a>(dirs: impl IntoIterator<Item = &'a Path>) -> Self {
+    let cache_path = BaseDirectories::with_prefix("n16-shell").place_cache_file(CACHE_FILE);
+
+    match cache_path {
+      Ok(cache_path) => Self::build_with_cache(dirs, Some(&cache_path)),
+      Err(error) => {
+        eprintln!("Failed to create icon index cache directory: {error}");
+        Self::build_with_cache(dirs, None)
+      }
+    }
+  }
+
+  /// [`IconIndex::build`] with the cache stored at `cache_path`, or without a cache
+  pub fn build_with_cache<'a>(
+    dirs: impl IntoIterator<Item = &'a Path>,
+    cache_path: Option<&Path>,
+  ) -> Self {
+    let mut cached = cache_path.and_then(read_cache).unwrap_or_default();
+
+    let mut directories = HashMap::new();
+    let mut changed = false;
+
+    for dir in dirs {
+      if directories.contains_key(dir) {
+        continue;
+      }
+
+      // Directories that don't exist are simply left out of the index
+      let Ok(mtime) = Mtime::of(dir) else {
+        changed |= cached.remove(dir).is_some();
+        continue;
+      };
+
+      let index = match cached.remove(dir) {
+        Some(index) if index.mtime == mtime => index,
+        _ => {
+          changed = true;
+          match DirIndex::scan(dir, mtime) {
+            Ok(index) => index,
+            Err(_) => continue,
+          }
+        }
+      };
+
+      directories.insert(dir.to_path_buf(), index);
+    }
+
+    // Directories that are no longer searched are dropped from the cache
+    changed |= !cached.is_empty();
+
+    let this = Self { directories };
+
+    if changed
+      && let Some(cache_path) = cache_path
+      && let Err(error) = this.write_cache(cache_path)
+    {
+      eprintln!("Failed to write icon index cache: {error}");
+    }
+
+    this
+  }
+
+  /// Find the preferred icon file named `icon_name` in `dir`.
+  /// `dir` must have been included when the index was built.
+  pub fn find(&self, icon_name: &str, dir: &Path) -> Option<PathBuf> {
+    let file_name = self.directories.get(dir)?.icons.get(icon_name)?.first()?;
+
+    Some(dir.join(file_name))
+  }
+
+  fn write_cache(&self, cache_path: &Path) -> io::Result<()> {
+    let cache = CacheFile {
+      version: CACHE_VERSION,
+      directories: self.directories.clone(),
+    };
+
+    let text = serde_json::to_vec(&cache)?;
+
+    // Write to a temporary file first so a concurrent reader never sees a partial cache
+    let tmp_path = cache_path.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&tmp_path, text)?;
+    fs::rename(tmp_path, cache_path)
+  }
+}
+
+fn read_cache(cache_path: &Path) -> Option<HashMap<PathBuf, DirIndex>> {
+  let text = fs::read(cache_path).ok()?;
+  let cache: CacheFile = serde_json::from_slice(&text).ok()?;
+
+  (cache.version == CACHE_VERSION).then_some(cache.directories)
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{fs::File, time::Duration};
+
+  use super::*;
+
+  fn touch(dir: &Path, file_name: &str) {
+    File::create(dir.join(file_name)).unwrap();
+  }
+
+  fn set_mtime(dir: &Path, mtime: std::time::SystemTime) {
+    File::open(dir).unwrap().set_modified(mtime).unwrap();
+  }
+
+  #[test]
+  fn scan_indexes_icons_by_name() {
+    let dir = tempfile::tempdir().unwrap();
+    for file_name in [
+      "firefox.svg",
+      "firefox.png",
+      "firefox.xpm",
+      ".hidden",
+      "README",
+    ] {
+      touch(dir.path(), file_name);
+    }
+
+    let index = DirIndex::scan(dir.path(), Mtime::of(dir.path()).unwrap()).unwrap();
+
+    assert_eq!(index.icons.len(), 1);
+    assert_eq!(
+      index.icons["firefox"],
+      ["firefox.png", "firefox.svg", "firefox.xpm"]
+    );
+  }
+
+  #[test]
+  fn scan_records_the_given_mtime() {
+    let dir = tempfile::tempdir().unwrap();
+    let mtime = Mtime { secs: 1, nanos: 2 };
+
+    let index = DirIndex::scan(dir.path(), mtime).unwrap();
+
+    assert_eq!(index.mtime, mtime);
+  }
+
+  #[test]
+  fn build_reuses_cache_until_mtime_changes() {
+    let icons = tempfile::tempdir().unwrap();
+    let cache = tempfile::tempdir().unwrap();
+    let cache_path = cache.path().join(CACHE_FILE);
+    let dir = icons.path();
+
+    touch(dir, "firefox.png");
+    let index = IconIndex::build_with_cache([dir], Some(&cache_path));
+    assert_eq!(index.find("firefox", dir), Some(dir.join("firefox.png")));
+    assert!(cache_path.exists());
+
+    // A new icon is not seen while the directory keeps the indexed mtime
+    let mtime = fs::metadata(dir).unwrap().modified().unwrap();
+    touch(dir, "kitty.png");
+    set_mtime(dir, mtime);
+
+    let index = IconIndex::build_with_cache([dir], Some(&cache_path));
+    assert_eq!(index.find("kitty", dir), None);
+
+    // Once the mtime changes, the directory is rescanned
+    set_mtime(dir, mtime + Duration::from_secs(1));
+
+    let index = IconIndex::build_with_cache([dir], Some(&cache_path));
+    assert_eq!(index.find("kitty", dir), Some(dir.join("kitty.png")));
+  }
+
+  #[test]
+  fn build_drops_missing_directories() {
+    let icons = tempfile::tempdir().unwrap();
+    let cache = tempfile::tempdir().unwrap();
+    let cache_path = cache.path().join(CACHE_FILE);
+    let dir = icons.path().join("48x48");
+
+    fs::create_dir(&dir).unwrap();
+    touch(&dir, "firefox.png");
+    IconIndex::build_with_cache([dir.as_path()], Some(&cache_path));
+    assert!(read_cache(&cache_path).unwrap().contains_key(&dir));
+
+    fs::remove_dir_all(&dir).unwrap();
+
+    let index = IconIndex::build_with_cache([dir.as_path()], Some(&cache_path));
+    assert_eq!(index.find("firefox", &dir), None);
+    assert!(!read_cache(&cache_path).unwrap().contains_key(&dir));
+  }
+
+  #[test]
+  fn build_ignores_cache_of_other_version() {
+    let icons = tempfile::tempdir().unwrap();
+    let cache = tempfile::tempdir().unwrap();
+    let cache_path = cache.path().join(CACHE_FILE);
+    let dir = icons.path();
+
+    touch(dir, "firefox.png");
+    IconIndex::build_with_cache([dir], Some(&cache_path));
+
+    let mut cache_file: CacheFile =
+      serde_json::from_slice(&fs::read(&cache_path).unwrap()).unwrap();
+    cache_file.version = CACHE_VERSION + 1;
+    fs::write(&cache_path, serde_json::to_vec(&cache_file).unwrap()).unwrap();
+
+    assert!(read_cache(&cache_path).is_none());
+  }
+}
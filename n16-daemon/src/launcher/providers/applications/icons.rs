@@ -1,33 +1,18 @@
 use std::{
   collections::{HashMap, HashSet},
-  fs, io,
-  path::{Path, PathBuf},
+  path::PathBuf,
 };
 
 use freedesktop_desktop_entry::DesktopEntry;
 
+use super::icon_index::IconIndex;
 use super::icon_theme::{FALLBACK_THEME, IconTheme, IconType};
 
-fn find_icon_in_dir(icon_name: &str, dir: &Path) -> io::Result<Option<PathBuf>> {
-  for entry in fs::read_dir(dir)? {
-    let path = entry?.path();
-
-    let Some(file_stem) = path.file_stem() else {
-      return Ok(None);
-    };
-
-    if file_stem == icon_name {
-      return Ok(Some(path));
-    }
-  }
-
-  Ok(None)
-}
-
 fn get_icon_for_theme<'s>(
   icon_name: &str,
   desired_theme: &'s str,
   icon_themes: &'s HashMap<String, IconTheme>,
+  icon_index: &IconIndex,
   searched_themes: &mut HashSet<&'s str>,
 ) -> Option<PathBuf> {
   if let Some(icon_theme) = icon_themes.get(desired_theme) {
@@ -36,7 +21,7 @@ fn get_icon_for_theme<'s>(
     let mut best_size_delta = i32::MAX;
 
     for size_dir in icon_theme.directories() {
-      let Ok(Some(icon)) = find_icon_in_dir(icon_name, size_dir.full_path()) else {
+      let Some(icon) = icon_index.find(icon_name, size_dir.full_path()) else {
         continue;
       };
 
@@ -81,7 +66,13 @@ fn get_icon_for_theme<'s>(
         continue;
       }
 
-      if let Some(icon) = get_icon_for_theme(icon_name, inherited, icon_themes, searched_themes) {
+      if let Some(icon) = get_icon_for_theme(
+        icon_name,
+        inherited,
+        icon_themes,
+        icon_index,
+        searched_themes,
+      ) {
         return Some(icon);
       }
 
@@ -92,7 +83,13 @@ fn get_icon_for_theme<'s>(
   }
 
   if !searched_themes.contains(FALLBACK_THEME) {
-    return get_icon_for_theme(icon_name, FALLBACK_THEME, icon_themes, searched_themes);
+    return get_icon_for_theme(
+      icon_name,
+      FALLBACK_THEME,
+      icon_themes,
+      icon_index,
+      searched_themes,
+    );
   }
 
   None
@@ -102,6 +99,7 @@ pub fn get_icon(
   entry: &DesktopEntry,
   desired_theme: &str,
   icon_themes: &HashMap<String, IconTheme>,
+  icon_index: &IconIndex,
   data_dirs: &[PathBuf],
 ) -> Option<PathBuf> {
   let icon_name = entry.icon()?;
@@ -112,14 +110,20 @@ pub fn get_icon(
   }
 
   // Search in XDG icon dirs
-  let icon = get_icon_for_theme(icon_name, desired_theme, icon_themes, &mut HashSet::new());
+  let icon = get_icon_for_theme(
+    icon_name,
+    desired_theme,
+    icon_themes,
+    icon_index,
+    &mut HashSet::new(),
+  );
   if let Some(icon) = icon {
     return Some(icon);
   }
 
   // Use icons in the pixmaps dir as a fallback
   for data_dir in data_dirs {
-    let Ok(Some(icon)) = find_icon_in_dir(icon_name, &data_dir.join("pixmaps")) else {
+    let Some(icon) = icon_index.find(icon_name, &data_dir.join("pixmaps")) else {
       continue;
     };
 
@@ -1,31 +1,47 @@
-use std::{ffi::OsStr, path::PathBuf, process};
+use std::{ffi::OsStr, path::PathBuf, sync::Arc};
 
 use async_trait::async_trait;
 use freedesktop_desktop_entry::{self as desktop};
 use iced::widget::{image, svg};
 use itertools::Itertools;
+use n16_core::config::{APPLICATIONS_PROVIDER_ID, ApplicationsConfig, LaunchBackend};
 use xdg::BaseDirectories;
 
-use icon_theme::get_icon_themes;
+pub use icon_index::IconIndex;
+use icon_theme::{IconTheme, get_icon_themes};
 use icons::get_icon;
 
-use crate::launcher::providers::{
-  ExecutionFinishAction, Match, MatchIcon, Provider, ProviderInfo, ProviderType,
+use crate::launcher::{
+  launch::{AppLaunch, launch_app},
+  providers::{
+    ExecutionFinishAction, Match, MatchIcon, Provider, ProviderInfo, Static, StaticProvider,
+  },
 };
 
+mod icon_index;
 mod icon_theme;
 mod icons;
 
 #[derive(Debug, Clone)]
 pub struct ApplicationInfo {
+  /// Desktop file id, such as `org.gnome.Nautilus`
+  id: String,
   name: String,
   icon: Option<MatchIcon>,
   command: Option<String>,
+  dbus_activatable: bool,
 }
 
 impl ApplicationInfo {
-  pub fn new(name: String, icon: Option<PathBuf>, command: Option<String>) -> Self {
+  pub fn new(
+    id: String,
+    name: String,
+    icon: Option<PathBuf>,
+    command: Option<String>,
+    dbus_activatable: bool,
+  ) -> Self {
     Self {
+      id,
       name,
       icon: icon.map(|icon_path| {
         if matches!(icon_path.extension().and_then(OsStr::to_str), Some("svg")) {
@@ -35,8 +51,14 @@ impl ApplicationInfo {
         }
       }),
       command,
+      dbus_activatable,
     }
   }
+
+  /// Whether the application can be launched by D-Bus activation or its `Exec` key
+  fn launchable(&self) -> bool {
+    self.dbus_activatable || self.command.is_some()
+  }
 }
 
 fn get_data_dirs(env: &BaseDirectories) -> Vec<PathBuf> {
@@ -54,6 +76,15 @@ fn get_data_dirs(env: &BaseDirectories) -> Vec<PathBuf> {
 pub fn get_application_info() -> Vec<ApplicationInfo> {
   let data_dirs = get_data_dirs(&BaseDirectories::new());
   let icon_themes = get_icon_themes(&data_dirs);
+
+  let theme_dirs = icon_themes
+    .values()
+    .flat_map(IconTheme::directories)
+    .map(|dir| dir.full_path().to_path_buf());
+  let pixmap_dirs = data_dirs.iter().map(|data_dir| data_dir.join("pixmaps"));
+  let icon_dirs: Vec<PathBuf> = theme_dirs.chain(pixmap_dirs).collect();
+  let icon_index = IconIndex::build(icon_dirs.iter().map(PathBuf::as_path));
+
   let locales = desktop::get_languages_from_env();
 
   let entries =
@@ -66,91 +97,94 @@ pub fn get_application_info() -> Vec<ApplicationInfo> {
         return None;
       }
 
-      let icon = get_icon(&entry, "hicolor", &icon_themes, &data_dirs);
+      let icon = get_icon(&entry, "hicolor", &icon_themes, &icon_index, &data_dirs);
 
       let name = entry.name(&locales)?;
       let exec = entry.exec();
+      let dbus_activatable = entry.desktop_entry("DBusActivatable") == Some("true");
 
       Some(ApplicationInfo::new(
+        entry.id().to_string(),
         name.to_string(),
         icon,
         exec.map(str::to_string),
+        dbus_activatable,
       ))
     })
     .collect()
 }
 
 pub struct ApplicationProvider {
-  application_info: Vec<ApplicationInfo>,
+  application_info: Vec<Arc<ApplicationInfo>>,
+  launch_backend: LaunchBackend,
 }
 
 #[async_trait]
 impl Provider for ApplicationProvider {
-  fn init() -> (ProviderInfo, Self)
+  type Data = Arc<ApplicationInfo>;
+  type Config = ApplicationsConfig;
+  type Kind = Static;
+
+  fn init(config: ApplicationsConfig) -> (ProviderInfo, Self)
   where
     Self: Sized,
   {
     (
       ProviderInfo {
-        id: "n16/applications".into(),
+        id: APPLICATIONS_PROVIDER_ID.into(),
         name: "Applications".into(),
         priorty: 0,
-        provider_type: ProviderType::Static,
+        max_results: None,
       },
       Self {
-        application_info: get_application_info(),
+        application_info: get_application_info().into_iter().map(Arc::new).collect(),
+        launch_backend: config.launch_backend(),
       },
     )
   }
 
-  async fn matches(&self) -> Vec<Match> {
+  async fn execute_match(
+    &self,
+    selected_match: Match<Arc<ApplicationInfo>>,
+  ) -> ExecutionFinishAction<Arc<ApplicationInfo>> {
+    let application_info = &selected_match.data;
+
+    if !application_info.launchable() {
+      return ExecutionFinishAction::Close;
+    }
+
+    let command: Vec<&str> = application_info
+      .command
+      .iter()
+      .flat_map(|command| command.split_ascii_whitespace())
+      .filter(|s| !s.starts_with('%'))
+      .collect();
+
+    let app = AppLaunch {
+      name: &application_info.name,
+      id: &application_info.id,
+      command,
+      dbus_activatable: application_info.dbus_activatable,
+    };
+
+    launch_app(&app, self.launch_backend).await
+  }
+}
+
+#[async_trait]
+impl StaticProvider for ApplicationProvider {
+  async fn matches(&self) -> Vec<Match<Arc<ApplicationInfo>>> {
     return self
       .application_info
       .iter()
-      .enumerate()
-      .map(|(id, info)| Match {
+      .map(|info| Match {
         title: info.name.clone(),
         description: None,
         icon: info.icon.clone(),
         keywords: Vec::new(),
-        executable: info.command.is_some(),
-        id: id as u64,
+        executable: info.launchable(),
+        data: Arc::clone(info),
       })
       .collect();
   }
-
-  async fn matches_dynamic(&self, _search_text: String) -> Vec<Match> {
-    unimplemented!()
-  }
-
-  async fn execute_match(&self, selected_match: Match) -> ExecutionFinishAction {
-    #[expect(
-      clippy::cast_possible_truncation,
-      reason = "Ids come from array index, which is usize"
-    )]
-    let Some(application_info) = self.application_info.get(selected_match.id as usize) else {
-      return ExecutionFinishAction::Close;
-    };
-
-    let Some(command) = &application_info.command else {
-      return ExecutionFinishAction::Close;
-    };
-
-    let args: Vec<&str> = command
-      .split_ascii_whitespace()
-      .filter(|s| !s.starts_with('%'))
-      .collect();
-
-    if let Err(error) = process::Command::new(args[0])
-      .args(&args[1..])
-      .stdin(process::Stdio::null())
-      .stdout(process::Stdio::null())
-      .stderr(process::Stdio::null())
-      .spawn()
-    {
-      eprintln!("{error}");
-    }
-
-    ExecutionFinishAction::Close
-  }
 }
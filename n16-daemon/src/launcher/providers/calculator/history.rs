@@ -0,0 +1,106 @@
+//! Persistent history of executed calculations, stored under `$XDG_STATE_HOME/n16-shell`.
+
+use std::{
+  fs, io,
+  path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use xdg::BaseDirectories;
+
+const HISTORY_FILE: &str = "calculator-history.json";
+
+/// Maximum number of calculations kept in the history
+const MAX_ENTRIES: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Calculation {
+  pub expression: String,
+  pub result: String,
+}
+
+/// History of calculations, saved to a file after every change unless it is kept in memory only
+#[derive(Debug, Default)]
+pub struct History {
+  /// Calculations ordered from newest to oldest
+  entries: Vec<Calculation>,
+  path: Option<PathBuf>,
+}
+
+impl History {
+  /// Load the history file, starting with an empty history if it is missing or invalid
+  pub fn load() -> Self {
+    match BaseDirectories::with_prefix("n16-shell").place_state_file(HISTORY_FILE) {
+      Ok(path) => Self::load_from(path),
+      Err(error) => {
+        eprintln!("Failed to create calculator history directory: {error}");
+        Self::default()
+      }
+    }
+  }
+
+  fn load_from(path: PathBuf) -> Self {
+    let entries = fs::read(&path)
+      .ok()
+      .and_then(|text| serde_json::from_slice(&text).ok())
+      .unwrap_or_default();
+
+    Self {
+      entries,
+      path: Some(path),
+    }
+  }
+
+  pub fn entries(&self) -> &[Calculation] {
+    &self.entries
+  }
+
+  /// Add a calculation as the newest entry and save the history
+  pub fn push(&mut self, calculation: Calculation) {
+    self.entries.retain(|entry| *entry != calculation);
+    self.entries.insert(0, calculation);
+    self.entries.truncate(MAX_ENTRIES);
+
+    if let Some(path) = &self.path
+      && let Err(error) = self.save(path)
+    {
+      eprintln!("Failed to save calculator history: {error}");
+    }
+  }
+
+  fn save(&self, path: &Path) -> io::Result<()> {
+    let text = serde_json::to_vec(&self.entries)?;
+
+    // Write to a temporary file first so a crash never leaves a partial history
+    let tmp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&tmp_path, text)?;
+    fs::rename(tmp_path, path)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn calculation(expression: &str, result: &str) -> Calculation {
+    Calculation {
+      expression: expression.into(),
+      result: result.into(),
+    }
+  }
+
+  #[test]
+  fn push_saves_newest_first_without_duplicates() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(HISTORY_FILE);
+
+    let mut history = History::load_from(path.clone());
+    history.push(calculation("1 + 1", "2"));
+    history.push(calculation("2 * 3", "6"));
+    history.push(calculation("1 + 1", "2"));
+
+    let expected = [calculation("1 + 1", "2"), calculation("2 * 3", "6")];
+    assert_eq!(history.entries(), expected);
+    assert_eq!(History::load_from(path).entries(), expected);
+  }
+}
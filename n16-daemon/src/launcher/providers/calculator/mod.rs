@@ -0,0 +1,411 @@
+mod history;
+
+use std::{
+  error::Error,
+  fmt,
+  sync::{Arc, Mutex},
+  time::Instant,
+};
+
+use async_trait::async_trait;
+use fend_core::{Context, ExchangeRateFnV2, ExchangeRateFnV2Options, Interrupt};
+use n16_core::config::{CALCULATOR_PROVIDER_ID, CalculatorConfig, ExchangeRates};
+use tokio_util::sync::CancellationToken;
+use wl_clipboard_rs::copy::{self, MimeType, Source};
+
+use crate::launcher::providers::{
+  Dynamic, DynamicProvider, ExecutionFinishAction, Match, Provider, ProviderInfo,
+};
+use history::{Calculation, History};
+
+/// Queries starting with this prefix also show the calculation history
+const HISTORY_PREFIX: char = '=';
+
+/// Destination of copied calculator results
+pub trait Clipboard: fmt::Debug + Send + Sync {
+  fn copy(&self, text: String) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
+
+/// The Wayland clipboard, through the wlr or ext data control protocols
+#[derive(Debug)]
+struct WaylandClipboard;
+
+impl Clipboard for WaylandClipboard {
+  fn copy(&self, text: String) -> Result<(), Box<dyn Error + Send + Sync>> {
+    // The clipboard contents are served from a background thread until something else is copied
+    copy::Options::new().copy(Source::Bytes(text.into_bytes().into()), MimeType::Text)?;
+    Ok(())
+  }
+}
+
+#[derive(Debug)]
+pub struct CalculatorProvider {
+  config: CalculatorConfig,
+  /// Context holding the variables assigned during the current launcher session
+  context: Arc<Mutex<Context>>,
+  history: Arc<Mutex<History>>,
+  clipboard: Arc<dyn Clipboard>,
+}
+
+impl CalculatorProvider {
+  pub fn new(config: CalculatorConfig) -> Self {
+    Self {
+      context: Arc::new(Mutex::new(Self::new_context(&config))),
+      config,
+      history: Arc::new(Mutex::new(History::load())),
+      clipboard: Arc::new(WaylandClipboard),
+    }
+  }
+
+  fn new_context(config: &CalculatorConfig) -> Context {
+    let mut context = Context::new();
+
+    if !config.exchange_rates().is_empty() {
+      context.set_exchange_rate_handler_v2(StaticExchangeRates(config.exchange_rates().clone()));
+    }
+
+    context
+  }
+
+  fn interrupt(&self, cancel: Option<CancellationToken>) -> CalculationInterrupt {
+    CalculationInterrupt {
+      deadline: Instant::now() + self.config.timeout(),
+      cancel,
+    }
+  }
+
+  /// Preview the result of `input` without changing the session context.
+  /// The calculation is interrupted once `cancel` is cancelled.
+  pub async fn calculate(&self, input: &str, cancel: CancellationToken) -> Option<String> {
+    if input.len() < 3 {
+      return None;
+    }
+
+    let input = strip_let(input);
+    let interrupt = self.interrupt(Some(cancel));
+    let context = self.context.lock().unwrap().clone();
+
+    tokio::task::spawn_blocking(move || {
+      let res = fend_core::evaluate_preview_with_interrupt(&input, &context, &interrupt);
+
+      (!res.output_is_empty()).then(|| res.get_main_result().to_string())
+    })
+    .await
+    .ok()
+    .flatten()
+  }
+
+  /// Evaluate `expression` in the session context, keeping the variables it assigns
+  async fn commit(&self, expression: &str) {
+    let input = strip_let(expression);
+    let interrupt = self.interrupt(None);
+    let context = Arc::clone(&self.context);
+
+    let _ = tokio::task::spawn_blocking(move || {
+      let mut context = context.lock().unwrap();
+      fend_core::evaluate_with_interrupt(&input, &mut context, &interrupt)
+    })
+    .await;
+  }
+
+  fn history_matches(&self, filter: &str) -> Vec<Match<Calculation>> {
+    let history = self.history.lock().unwrap();
+
+    history
+      .entries()
+      .iter()
+      .filter(|entry| entry.expression.contains(filter) || entry.result.contains(filter))
+      .cloned()
+      .map(calculation_match)
+      .collect()
+  }
+
+  fn add_to_history(&self, calculation: Calculation) {
+    self.history.lock().unwrap().push(calculation);
+  }
+}
+
+/// Match showing the result as the title and the expression as its description
+fn calculation_match(calculation: Calculation) -> Match<Calculation> {
+  Match {
+    title: calculation.result.clone(),
+    description: Some(calculation.expression.clone()),
+    icon: None,
+    keywords: Vec::new(),
+    executable: true,
+    data: calculation,
+  }
+}
+
+/// fend assigns variables with `x = 1`, allow writing `let x = 1` as well
+fn strip_let(input: &str) -> String {
+  input
+    .split(';')
+    .map(|statement| {
+      let trimmed = statement.trim_start();
+      trimmed.strip_prefix("let ").unwrap_or(trimmed)
+    })
+    .collect::<Vec<_>>()
+    .join(";")
+}
+
+/// Whether `input` assigns a variable, like `x = 1` or `let x = 1`
+fn is_assignment(input: &str) -> bool {
+  input.split(';').any(|statement| {
+    let statement = statement.trim_start();
+    if statement.starts_with("let ") {
+      return true;
+    }
+
+    statement.split_once('=').is_some_and(|(name, value)| {
+      let name = name.trim();
+      !value.starts_with('=')
+        && name.chars().next().is_some_and(char::is_alphabetic)
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+    })
+  })
+}
+
+/// Stops a calculation once its time budget is used up, or once `cancel` is cancelled
+struct CalculationInterrupt {
+  deadline: Instant,
+  cancel: Option<CancellationToken>,
+}
+
+impl Interrupt for CalculationInterrupt {
+  fn should_interrupt(&self) -> bool {
+    Instant::now() >= self.deadline
+      || self
+        .cancel
+        .as_ref()
+        .is_some_and(CancellationToken::is_cancelled)
+  }
+}
+
+/// Exchange rates from the config, never fetched from the network
+struct StaticExchangeRates(ExchangeRates);
+
+impl ExchangeRateFnV2 for StaticExchangeRates {
+  fn relative_to_base_currency(
+    &self,
+    currency: &str,
+    _options: &ExchangeRateFnV2Options,
+  ) -> Result<f64, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    self
+      .0
+      .rate(currency)
+      .ok_or_else(|| format!("No exchange rate configured for {currency}").into())
+  }
+}
+
+#[async_trait]
+impl Provider for CalculatorProvider {
+  type Data = Calculation;
+  type Config = CalculatorConfig;
+  type Kind = Dynamic;
+
+  fn init(config: CalculatorConfig) -> (ProviderInfo, Self)
+  where
+    Self: Sized,
+  {
+    (
+      ProviderInfo {
+        id: CALCULATOR_PROVIDER_ID.into(),
+        name: "Calculator".into(),
+        priorty: 10,
+        max_results: None,
+      },
+      Self::new(config),
+    )
+  }
+
+  /// Forget the variables assigned in the previous session
+  fn session_started(&self) {
+    *self.context.lock().unwrap() = Self::new_context(&self.config);
+  }
+
+  /// Copy the result to the clipboard. Assignments keep the launcher open with an empty query, so
+  /// the variable can be used in the next calculation.
+  async fn execute_match(
+    &self,
+    selected_match: Match<Calculation>,
+  ) -> ExecutionFinishAction<Calculation> {
+    let calculation = selected_match.data;
+    self.commit(&calculation.expression).await;
+
+    match tokio::task::spawn_blocking({
+      let result = calculation.result.clone();
+      let clipboard = Arc::clone(&self.clipboard);
+      move || clipboard.copy(result)
+    })
+    .await
+    {
+      Ok(Ok(())) => {}
+      Ok(Err(error)) => eprintln!("Failed to copy calculator result: {error}"),
+      Err(error) => eprintln!("Failed to copy calculator result: {error}"),
+    }
+
+    let assignment = is_assignment(&calculation.expression);
+    self.add_to_history(calculation);
+
+    if assignment {
+      ExecutionFinishAction::SetQuery(String::new())
+    } else {
+      ExecutionFinishAction::Close
+    }
+  }
+
+  /// Replace the search text with the result to continue calculating with it
+  async fn execute_match_alt(
+    &self,
+    selected_match: Match<Calculation>,
+  ) -> ExecutionFinishAction<Calculation> {
+    let calculation = selected_match.data;
+    self.commit(&calculation.expression).await;
+
+    let result = calculation.result.clone();
+    self.add_to_history(calculation);
+
+    ExecutionFinishAction::SetQuery(result)
+  }
+}
+
+#[async_trait]
+impl DynamicProvider for CalculatorProvider {
+  async fn matches_dynamic(
+    &self,
+    search_text: String,
+    cancel: CancellationToken,
+  ) -> Vec<Match<Calculation>> {
+    let (expression, show_history) = match search_text.strip_prefix(HISTORY_PREFIX) {
+      Some(expression) => (expression.trim(), true),
+      None => (search_text.as_str(), false),
+    };
+
+    let mut matches: Vec<Match<Calculation>> = self
+      .calculate(expression, cancel)
+      .await
+      .map(|result| {
+        calculation_match(Calculation {
+          expression: expression.to_string(),
+          result,
+        })
+      })
+      .into_iter()
+      .collect();
+
+    if show_history {
+      matches.extend(self.history_matches(expression));
+    }
+
+    matches
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Records copied text together with the provider state at the time of the copy
+  #[derive(Debug)]
+  struct MockClipboard {
+    context: Arc<Mutex<Context>>,
+    history: Arc<Mutex<History>>,
+    /// Copied text, the value of `x` and the number of history entries when it was copied
+    copies: Mutex<Vec<(String, String, usize)>>,
+  }
+
+  impl Clipboard for MockClipboard {
+    fn copy(&self, text: String) -> Result<(), Box<dyn Error + Send + Sync>> {
+      let mut context = self.context.lock().unwrap().clone();
+      let x = fend_core::evaluate("x", &mut context)
+        .map_or_else(|error| error, |res| res.get_main_result().to_string());
+      let history_len = self.history.lock().unwrap().entries().len();
+
+      self.copies.lock().unwrap().push((text, x, history_len));
+      Ok(())
+    }
+  }
+
+  fn provider() -> (CalculatorProvider, Arc<MockClipboard>) {
+    let config = CalculatorConfig::default();
+    let context = Arc::new(Mutex::new(CalculatorProvider::new_context(&config)));
+    let history = Arc::new(Mutex::new(History::default()));
+    let clipboard = Arc::new(MockClipboard {
+      context: Arc::clone(&context),
+      history: Arc::clone(&history),
+      copies: Mutex::default(),
+    });
+
+    let provider = CalculatorProvider {
+      config,
+      context,
+      history,
+      clipboard: Arc::clone(&clipboard) as Arc<dyn Clipboard>,
+    };
+
+    (provider, clipboard)
+  }
+
+  fn calculation_match(expression: &str, result: &str) -> Match<Calculation> {
+    super::calculation_match(Calculation {
+      expression: expression.into(),
+      result: result.into(),
+    })
+  }
+
+  #[tokio::test]
+  async fn execute_commits_then_copies_then_records_history() {
+    let (provider, clipboard) = provider();
+
+    provider
+      .execute_match(calculation_match("x = 6 * 7", "42"))
+      .await;
+
+    // `x` was assigned before the copy, and the history only updated after it
+    assert_eq!(
+      *clipboard.copies.lock().unwrap(),
+      [("42".to_string(), "42".to_string(), 0)]
+    );
+    assert_eq!(
+      provider.history.lock().unwrap().entries(),
+      [Calculation {
+        expression: "x = 6 * 7".into(),
+        result: "42".into(),
+      }]
+    );
+  }
+
+  #[test]
+  fn assignments_are_detected() {
+    assert!(is_assignment("let x = 5"));
+    assert!(is_assignment("x = 5"));
+    assert!(is_assignment("rate_2 = 3; rate_2 * 4"));
+    assert!(!is_assignment("5 + 5"));
+    assert!(!is_assignment("1 + 1 = 2"));
+    assert!(!is_assignment("x == 5"));
+  }
+
+  #[tokio::test]
+  async fn assignment_keeps_the_session() {
+    let (provider, _) = provider();
+
+    let action = provider
+      .execute_match(calculation_match("let x = 5", "5"))
+      .await;
+
+    assert!(matches!(action, ExecutionFinishAction::SetQuery(query) if query.is_empty()));
+    assert_eq!(
+      provider
+        .calculate("x + 1", CancellationToken::new())
+        .await
+        .as_deref(),
+      Some("6")
+    );
+
+    let action = provider
+      .execute_match(calculation_match("x * 2", "10"))
+      .await;
+    assert!(matches!(action, ExecutionFinishAction::Close));
+  }
+}
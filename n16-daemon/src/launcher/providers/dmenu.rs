@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use n16_core::config::ProviderSettings;
+use n16_ipc::launcher::DmenuSelection;
+
+use crate::launcher::providers::{
+  ExecutionFinishAction, Match, Provider, ProviderInfo, Static, StaticProvider,
+};
+
+/// Provides the items of a dmenu request and reports the chosen one.
+pub struct DmenuProvider {
+  items: Vec<String>,
+  selection_tx: async_channel::Sender<DmenuSelection>,
+}
+
+impl DmenuProvider {
+  pub fn new(items: Vec<String>, selection_tx: async_channel::Sender<DmenuSelection>) -> Self {
+    Self {
+      items,
+      selection_tx,
+    }
+  }
+
+  pub fn info() -> ProviderInfo {
+    ProviderInfo {
+      id: "n16/dmenu".into(),
+      name: "dmenu".into(),
+      priorty: 0,
+      max_results: None,
+    }
+  }
+}
+
+#[async_trait]
+impl Provider for DmenuProvider {
+  /// The selection reported when the item is chosen
+  type Data = DmenuSelection;
+  type Config = ProviderSettings;
+  type Kind = Static;
+
+  /// Creates a provider without any items.
+  /// Use [`DmenuProvider::new`] to provide the items of a dmenu request.
+  fn init(_config: ProviderSettings) -> (ProviderInfo, Self)
+  where
+    Self: Sized,
+  {
+    let (selection_tx, _) = async_channel::bounded(1);
+    (Self::info(), Self::new(Vec::new(), selection_tx))
+  }
+
+  async fn execute_match(
+    &self,
+    selected_match: Match<DmenuSelection>,
+  ) -> ExecutionFinishAction<DmenuSelection> {
+    let _ = self.selection_tx.send(selected_match.data).await;
+
+    ExecutionFinishAction::Close
+  }
+}
+
+#[async_trait]
+impl StaticProvider for DmenuProvider {
+  async fn matches(&self) -> Vec<Match<DmenuSelection>> {
+    self
+      .items
+      .iter()
+      .enumerate()
+      .map(|(index, item)| Match {
+        title: item.clone(),
+        description: None,
+        icon: None,
+        keywords: Vec::new(),
+        executable: true,
+        data: DmenuSelection {
+          index: Some(index),
+          text: item.clone(),
+        },
+      })
+      .collect()
+  }
+}
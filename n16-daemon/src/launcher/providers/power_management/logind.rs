@@ -0,0 +1,16 @@
+use zbus::Connection;
+
+const DESTINATION: &str = "org.freedesktop.login1";
+const PATH: &str = "/org/freedesktop/login1";
+const INTERFACE: &str = "org.freedesktop.login1.Manager";
+
+/// Call a logind `Can*` method such as `CanHibernate`, returning whether the action is available.
+/// Actions needing authentication (`challenge`) count as available.
+pub async fn can(connection: &Connection, method: &str) -> zbus::Result<bool> {
+  let reply = connection
+    .call_method(Some(DESTINATION), PATH, Some(INTERFACE), method, &())
+    .await?;
+  let result: String = reply.body().deserialize()?;
+
+  Ok(matches!(result.as_str(), "yes" | "challenge"))
+}
@@ -0,0 +1,241 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use n16_core::config::{POWER_MANAGEMENT_PROVIDER_ID, PowerAction, PowerManagementConfig};
+use tokio::sync::OnceCell;
+
+use crate::launcher::{
+  launch::launch,
+  providers::{ExecutionFinishAction, Match, Provider, ProviderInfo, Static, StaticProvider},
+};
+
+mod logind;
+
+/// Time given to confirm a destructive action
+const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct PowerManagementProvider {
+  actions: Vec<Arc<PowerAction>>,
+  /// Matches of the actions available on this system, looked up once
+  matches: OnceCell<Vec<Match<Arc<PowerAction>>>>,
+  confirm: bool,
+}
+
+impl PowerManagementProvider {
+  pub fn new(config: &PowerManagementConfig) -> Self {
+    Self {
+      actions: config.actions().into_iter().map(Arc::new).collect(),
+      matches: OnceCell::new(),
+      confirm: config.confirm(),
+    }
+  }
+
+  /// Matches of the actions that logind on the system bus doesn't report as unavailable
+  async fn system_matches(&self) -> Vec<Match<Arc<PowerAction>>> {
+    match zbus::Connection::system().await {
+      Ok(connection) => self.available_matches(Some(&connection)).await,
+      Err(error) => {
+        eprintln!("Failed to connect to the system bus: {error}");
+        self.available_matches(None).await
+      }
+    }
+  }
+
+  /// Matches of the actions that logind doesn't report as unavailable.
+  /// Every action is kept if logind can't be reached.
+  async fn available_matches(
+    &self,
+    connection: Option<&zbus::Connection>,
+  ) -> Vec<Match<Arc<PowerAction>>> {
+    let mut matches = Vec::new();
+
+    for action in &self.actions {
+      if let (Some(connection), Some(method)) = (connection, action.logind_check()) {
+        match logind::can(connection, method).await {
+          Ok(false) => continue,
+          Ok(true) => {}
+          Err(error) => eprintln!("Failed to call logind {method}: {error}"),
+        }
+      }
+
+      matches.push(Match {
+        title: action.title().into(),
+        description: None,
+        icon: None,
+        keywords: action.keywords().to_vec(),
+        executable: true,
+        data: Arc::clone(action),
+      });
+    }
+
+    matches
+  }
+}
+
+#[async_trait]
+impl Provider for PowerManagementProvider {
+  type Data = Arc<PowerAction>;
+  type Config = PowerManagementConfig;
+  type Kind = Static;
+
+  fn init(config: PowerManagementConfig) -> (ProviderInfo, Self)
+  where
+    Self: Sized,
+  {
+    (
+      ProviderInfo {
+        id: POWER_MANAGEMENT_PROVIDER_ID.into(),
+        name: "Power Management".into(),
+        priorty: -1,
+        max_results: None,
+      },
+      Self::new(&config),
+    )
+  }
+
+  async fn execute_match(
+    &self,
+    selected_match: Match<Arc<PowerAction>>,
+  ) -> ExecutionFinishAction<Arc<PowerAction>> {
+    let action = &selected_match.data;
+
+    if self.confirm && action.destructive() {
+      return ExecutionFinishAction::RequestConfirmation {
+        action: action.title().into(),
+        timeout: CONFIRMATION_TIMEOUT,
+      };
+    }
+
+    launch(action.title(), action.command())
+  }
+
+  async fn execute_confirmed(
+    &self,
+    selected_match: Match<Arc<PowerAction>>,
+  ) -> ExecutionFinishAction<Arc<PowerAction>> {
+    let action = &selected_match.data;
+    launch(action.title(), action.command())
+  }
+}
+
+#[async_trait]
+impl StaticProvider for PowerManagementProvider {
+  async fn matches(&self) -> Vec<Match<Arc<PowerAction>>> {
+    self
+      .matches
+      .get_or_init(|| self.system_matches())
+      .await
+      .clone()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_bus::TestBus;
+
+  struct MockLogind;
+
+  #[expect(
+    clippy::unused_self,
+    reason = "D-Bus methods are called on the served object"
+  )]
+  #[zbus::interface(name = "org.freedesktop.login1.Manager")]
+  impl MockLogind {
+    fn can_power_off(&self) -> &'static str {
+      "yes"
+    }
+
+    fn can_reboot(&self) -> &'static str {
+      "challenge"
+    }
+
+    fn can_reboot_to_firmware_setup(&self) -> &'static str {
+      "na"
+    }
+
+    fn can_suspend(&self) -> &'static str {
+      "yes"
+    }
+
+    fn can_hibernate(&self) -> &'static str {
+      "no"
+    }
+
+    fn can_suspend_then_hibernate(&self) -> &'static str {
+      "no"
+    }
+  }
+
+  fn titles(matches: &[Match<Arc<PowerAction>>]) -> Vec<&str> {
+    matches.iter().map(|m| m.title.as_str()).collect()
+  }
+
+  #[tokio::test]
+  async fn can_reads_logind_answer() {
+    let bus = TestBus::start();
+    let _logind = bus
+      .serve(
+        "org.freedesktop.login1",
+        "/org/freedesktop/login1",
+        MockLogind,
+      )
+      .await;
+    let connection = bus.connect().await;
+
+    assert!(logind::can(&connection, "CanPowerOff").await.unwrap());
+    assert!(logind::can(&connection, "CanReboot").await.unwrap());
+    assert!(!logind::can(&connection, "CanHibernate").await.unwrap());
+    assert!(
+      !logind::can(&connection, "CanRebootToFirmwareSetup")
+        .await
+        .unwrap()
+    );
+  }
+
+  #[tokio::test]
+  async fn unavailable_actions_are_hidden() {
+    let bus = TestBus::start();
+    let _logind = bus
+      .serve(
+        "org.freedesktop.login1",
+        "/org/freedesktop/login1",
+        MockLogind,
+      )
+      .await;
+    let connection = bus.connect().await;
+    let provider = PowerManagementProvider::new(&PowerManagementConfig::default());
+
+    let matches = provider.available_matches(Some(&connection)).await;
+
+    assert_eq!(
+      titles(&matches),
+      ["Lock", "Log Out", "Shut Down", "Reboot", "Sleep"]
+    );
+  }
+
+  #[tokio::test]
+  async fn every_action_is_kept_without_logind() {
+    let bus = TestBus::start();
+    let connection = bus.connect().await;
+    let provider = PowerManagementProvider::new(&PowerManagementConfig::default());
+
+    let matches = provider.available_matches(Some(&connection)).await;
+
+    assert_eq!(matches.len(), provider.actions.len());
+  }
+
+  #[tokio::test]
+  async fn destructive_action_asks_for_confirmation() {
+    let provider = PowerManagementProvider::new(&PowerManagementConfig::default());
+    let matches = provider.available_matches(None).await;
+    let shut_down = matches.iter().find(|m| m.title == "Shut Down").unwrap();
+
+    let action = provider.execute_match(shut_down.clone()).await;
+
+    assert!(matches!(
+      action,
+      ExecutionFinishAction::RequestConfirmation { action, .. } if action == "Shut Down"
+    ));
+  }
+}
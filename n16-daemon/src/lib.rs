@@ -3,16 +3,26 @@ use std::{ops::ControlFlow, pin::pin, process::ExitCode};
 use bar::Bar;
 use futures_lite::StreamExt;
 use launcher::LauncherDaemon;
-use n16_ipc::{Request, Response};
+use n16_ipc::{Request, Response, theme};
 
 use crate::{application::run_application, ipc::run_ipc_server};
 
+mod appearance;
 mod application;
 mod bar;
+mod events;
 mod ipc;
 mod launcher;
+#[cfg(test)]
+mod test_bus;
+
+/// Exposed for the benchmarks in `benches/`
+#[doc(hidden)]
+pub use launcher::providers::IconIndex;
 
 pub async fn run_daemon() -> ExitCode {
+  tokio::spawn(appearance::watch_portal());
+
   let mut applications = [
     run_application::<LauncherDaemon>(),
     run_application::<Bar>(),
@@ -22,6 +32,14 @@ pub async fn run_daemon() -> ExitCode {
     match request.kind() {
       Request::Version => request.reply(Response::version()),
       Request::Ping => request.reply(Response::Ping),
+      Request::Theme(theme::Request::Get) => {
+        let palette = appearance::palette();
+        request.reply(Response::Theme(theme::Response::Palette(palette)));
+      }
+      Request::Theme(theme::Request::SetMode(mode)) => {
+        appearance::set_mode(*mode);
+        request.reply(Response::Handled);
+      }
 
       _ => 'handler: {
         let mut request = request;
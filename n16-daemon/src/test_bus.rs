@@ -0,0 +1,97 @@
+//! A private `dbus-daemon` for tests of D-Bus clients against mock services.
+//!
+//! Tests using it require `dbus-daemon` to be installed, and fail without it.
+
+use std::{
+  fs,
+  io::{BufRead, BufReader},
+  process::{Child, Command, Stdio},
+};
+
+use tempfile::TempDir;
+use zbus::{Connection, connection};
+
+const CONFIG: &str = r#"<!DOCTYPE busconfig PUBLIC "-//freedesktop//DTD D-Bus Bus Configuration 1.0//EN"
+ "http://www.freedesktop.org/standards/dbus/1.0/busconfig.dtd">
+<busconfig>
+  <type>session</type>
+  <listen>unix:dir=SOCKET_DIR</listen>
+  <auth>EXTERNAL</auth>
+  <policy context="default">
+    <allow send_destination="*" eavesdrop="true"/>
+    <allow eavesdrop="true"/>
+    <allow own="*"/>
+  </policy>
+</busconfig>
+"#;
+
+pub struct TestBus {
+  daemon: Child,
+  address: String,
+  _dir: TempDir,
+}
+
+impl TestBus {
+  /// Start a bus, panicking if `dbus-daemon` can't be run
+  pub fn start() -> Self {
+    let dir = tempfile::tempdir().unwrap();
+    let config_path = dir.path().join("bus.conf");
+    let config = CONFIG.replace("SOCKET_DIR", dir.path().to_str().unwrap());
+    fs::write(&config_path, config).unwrap();
+
+    let mut daemon = Command::new("dbus-daemon")
+      .arg("--nofork")
+      .arg("--print-address")
+      .arg(format!("--config-file={}", config_path.display()))
+      .stdout(Stdio::piped())
+      .stderr(Stdio::null())
+      .spawn()
+      .unwrap_or_else(|error| {
+        panic!("Failed to run dbus-daemon, which the test requires: {error}")
+      });
+
+    let mut address = String::new();
+    BufReader::new(daemon.stdout.take().unwrap())
+      .read_line(&mut address)
+      .unwrap();
+
+    Self {
+      daemon,
+      address: address.trim().to_string(),
+      _dir: dir,
+    }
+  }
+
+  pub async fn connect(&self) -> Connection {
+    connection::Builder::address(self.address.as_str())
+      .unwrap()
+      .build()
+      .await
+      .unwrap()
+  }
+
+  /// Connect as the service `name`, serving `object` at `path`
+  pub async fn serve<I: zbus::object_server::Interface>(
+    &self,
+    name: &str,
+    path: &str,
+    object: I,
+  ) -> Connection {
+    connection::Builder::address(self.address.as_str())
+      .unwrap()
+      .name(name)
+      .unwrap()
+      .serve_at(path, object)
+      .unwrap()
+      .build()
+      .await
+      .unwrap()
+  }
+}
+
+impl Drop for TestBus {
+  fn drop(&mut self) {
+    let _ = self.daemon.kill();
+    let _ = self.daemon.wait();
+  }
+}
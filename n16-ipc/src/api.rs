@@ -2,15 +2,19 @@ use serde::{Deserialize, Serialize};
 
 pub mod bar;
 pub mod launcher;
+pub mod theme;
 
 /// Request sent to the n16 daemon
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub enum Request {
   Version,
   Ping,
+  /// Keep the connection open and receive an [`Event`] per line after the reply
+  EventStream,
 
   Launcher(launcher::Request),
   Bar(bar::Request),
+  Theme(theme::Request),
 }
 
 impl Request {
@@ -32,6 +36,7 @@ pub enum Response {
 
   Launcher(launcher::Response),
   Bar(bar::Response),
+  Theme(theme::Response),
 }
 
 impl Response {
@@ -46,6 +51,31 @@ impl Response {
   }
 }
 
+/// Event streamed to clients that sent [`Request::EventStream`]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub enum Event {
+  /// A program was started from the launcher
+  Launched {
+    /// Name of the launched application or action
+    name: String,
+    /// `xdg_activation_v1` token passed to the program, if one could be requested
+    activation_token: Option<String>,
+  },
+  /// A program started from the launcher could not be spawned
+  LaunchFailed {
+    /// Name of the launched application or action
+    name: String,
+    error: String,
+  },
+}
+
+impl Event {
+  /// Serialize this `Event` to json
+  pub fn to_json(&self) -> String {
+    serde_json::to_string(&self).unwrap()
+  }
+}
+
 /// Reply from the n16 daemon
 ///
 /// Every request gets one reply.
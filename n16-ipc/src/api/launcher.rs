@@ -1,9 +1,41 @@
 use serde::{Deserialize, Serialize};
 
+/// Unique id of a launcher provider, such as `n16/calculator`
+pub type ProviderId = String;
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub enum Request {
-  Open,
+  /// Open the launcher if it isn't already open
+  Open {
+    /// Only show matches from these providers, or from every provider if `None`
+    providers: Option<Vec<ProviderId>>,
+    /// Initial search text
+    query: Option<String>,
+    /// Text shown in the empty search input
+    placeholder: Option<String>,
+  },
+  /// Close the launcher if it is open, otherwise open it like [`Request::Open`]
+  Toggle {
+    providers: Option<Vec<ProviderId>>,
+    query: Option<String>,
+    placeholder: Option<String>,
+  },
   Close,
+  /// Show a list of items and reply with the one chosen by the user,
+  /// like `dmenu`. Replied to with [`Response::DmenuSelection`].
+  Dmenu(DmenuRequest),
+  /// Reload the config file and reinitialize the launcher providers
+  Reload,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DmenuRequest {
+  /// Items to choose from
+  pub items: Vec<String>,
+  /// Text shown in the empty search input
+  pub prompt: Option<String>,
+  /// Hide the typed text
+  pub password: bool,
 }
 
 impl From<Request> for super::Request {
@@ -24,7 +56,18 @@ impl TryFrom<super::Request> for Request {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
-pub enum Response {}
+pub enum Response {
+  /// The item chosen in response to [`Request::Dmenu`], or `None` if the launcher was closed.
+  DmenuSelection(Option<DmenuSelection>),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DmenuSelection {
+  /// Index of the chosen item, or `None` if the typed text was submitted instead
+  pub index: Option<usize>,
+  /// Text of the chosen item or the typed text
+  pub text: String,
+}
 
 impl Response {
   pub fn handled() -> super::Response {
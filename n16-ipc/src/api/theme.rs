@@ -0,0 +1,70 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Which of the configured light and dark themes is used
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ThemeMode {
+  Light,
+  Dark,
+  /// Follow the system preference from xdg-desktop-portal
+  #[default]
+  Auto,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub enum Request {
+  /// Get the colors of the active theme. Replied to with [`Response::Palette`].
+  Get,
+  /// Override the system light/dark preference, or follow it again with [`ThemeMode::Auto`]
+  SetMode(ThemeMode),
+}
+
+impl From<Request> for super::Request {
+  fn from(value: Request) -> Self {
+    super::Request::Theme(value)
+  }
+}
+
+impl TryFrom<super::Request> for Request {
+  type Error = super::Request;
+
+  fn try_from(value: super::Request) -> Result<Self, Self::Error> {
+    match value {
+      super::Request::Theme(theme_value) => Ok(theme_value),
+      _ => Err(value),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub enum Response {
+  /// Colors of the active theme, in response to [`Request::Get`]
+  Palette(Palette),
+}
+
+/// Colors of a theme, as `#rrggbb` hex strings
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Palette {
+  /// `base00` through `base17`
+  pub colors: BTreeMap<String, String>,
+  /// Color of each role, such as `accent`
+  pub roles: BTreeMap<String, String>,
+}
+
+impl From<Response> for super::Response {
+  fn from(value: Response) -> Self {
+    super::Response::Theme(value)
+  }
+}
+
+impl TryFrom<super::Response> for Response {
+  type Error = super::Response;
+
+  fn try_from(value: super::Response) -> Result<Self, Self::Error> {
+    match value {
+      super::Response::Theme(theme_value) => Ok(theme_value),
+      _ => Err(value),
+    }
+  }
+}
@@ -13,6 +13,8 @@
   libxrandr,
   wayland,
   libxkbcommon,
+  dbus,
+  util-linux,
 }: let
   icedDeps = [
     expat
@@ -40,6 +42,9 @@ in
 
     buildInputs = icedDeps;
 
+    # The D-Bus client tests run against a private bus, and launched programs are detached with setsid
+    nativeCheckInputs = [dbus util-linux];
+
     preBuild = ''
       export N16_COMPLETION_OUT_DIR=$out/share/bash-completion/completions
       mkdir -p $N16_COMPLETION_OUT_DIR